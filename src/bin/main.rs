@@ -1,33 +1,60 @@
-use std::sync::Arc;
+use std::{env, fs, process};
 
 use std::time::Instant;
 
-use comodules::{
-    comodule::{kcoalgebra::kCoalgebra, kcomodule::kComodule},
-    linalg::{field::F2, flat_matrix::FlatMatrix, grading::UniGrading},
-    resolution::Resolution,
-};
+use comodules::dispatch::resolve;
+
+const USAGE: &str = "Usage: comodule <coalgebra file> [comodule file] [--s <filtration>] [--limit <max grading>] [--out <json file>]";
 
 fn main() {
     let start = Instant::now();
 
-    let input = include_str!("../../examples/polynomial/A(1).txt");
-    const MAX_GRADING: i32 = 60;
-    let (coalgebra, translate) = kCoalgebra::parse(input, MAX_GRADING).unwrap();
-    // let coalgebra = Arc::new(kCoalgebra::parse(input, MAX_GRADING).unwrap().0);
-
-    let coalgebra = Arc::new(coalgebra);
-    let input = include_str!("../../examples/comodule/A(1).txt");
-
-    let comod = kComodule::parse(input, coalgebra, &translate, MAX_GRADING).unwrap();
-    let mut res: Resolution<UniGrading, kComodule<UniGrading, F2, FlatMatrix<F2>>> =
-        Resolution::new(comod);
-
-    res.resolve_to_s_with_print(3, MAX_GRADING);
+    let mut files = vec![];
+    let mut s = 3;
+    let mut max_grading = 60;
+    let mut out = "page.json".to_owned();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--s" | "--limit" | "--out" => {
+                let value = args
+                    .next()
+                    .unwrap_or_else(|| exit(&format!("Missing value for {}", arg)));
+                match arg.as_str() {
+                    "--s" => s = value.parse().unwrap_or_else(|_| exit("Invalid filtration")),
+                    "--limit" => {
+                        max_grading = value
+                            .parse()
+                            .unwrap_or_else(|_| exit("Invalid max grading"))
+                    }
+                    _ => out = value,
+                }
+            }
+            _ => files.push(arg),
+        }
+    }
+
+    let (coalgebra, comodule) = match files.as_slice() {
+        [coalgebra] => (read(coalgebra), None),
+        [coalgebra, comodule] => (read(coalgebra), Some(read(comodule))),
+        _ => exit(USAGE),
+    };
+
+    let name = files.last().unwrap();
+    let page = resolve(&coalgebra, comodule.as_deref(), s, max_grading, name, true)
+        .unwrap_or_else(|e| exit(&e));
+
+    let _ = page.save_to_json(&out);
 
-    let page = res.generate_sseq("?");
+    println!("\nProgram took: {:.2?}", start.elapsed());
+}
 
-    let _ = page.save_to_json("page.json");
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| exit(&format!("Could not read {}: {}", path, e)))
+}
 
-    println!("\nProgram took: {:.2?}", start.elapsed());
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
 }
@@ -1,18 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use ahash::RandomState;
-use itertools::Itertools;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::linalg::{
+    echelon::EchelonForm,
     field::{CRing, Field, F2},
-    graded::{GradedLinearMap, GradedVectorSpace},
+    graded::{BasisIndex, GradedLinearMap, GradedVectorSpace},
     grading::{Grading, UniGrading},
     matrix::Matrix,
     row_matrix::RowMatrix,
 };
 
-use super::{kcomodule::kBasisElement, ktensor::kTensor, traits::Tensor};
+use super::{kcomodule::kBasisElement, ktensor::kTensor, traits::Tensor, validate::terms};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -21,31 +21,172 @@ pub struct kCoalgebra<G: Grading, F: Field, M: Matrix<F>> {
     pub space: GradedVectorSpace<G, kBasisElement>,
     pub coaction: GradedLinearMap<G, F, M>,
     pub tensor: kTensor<G>,
+    /// Primitives in every grade as the rows of a matrix in reduced row echelon form,
+    /// filled in by `set_primitives`
+    pub primitives: GradedLinearMap<G, F, M>,
+    /// Product `C ⊗ C -> C`, indexed by the tensor indices of `tensor` in every grade.
+    /// `None` when only the coalgebra structure is known.
+    pub product: Option<GradedLinearMap<G, F, M>>,
 }
 
 impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
+    /// Coaction in `grade` with the terms `1|x` and `x|1` left out
+    pub fn reduced_coaction(&self, grade: &G) -> M {
+        let mut map = self.coaction.maps[grade].clone();
+        let unit = (G::zero(), 0);
+        for t_id in 0..map.codomain() {
+            if let Some((l, r)) = self.tensor.deconstruct.get(&(*grade, t_id)) {
+                if *l == unit || *r == unit {
+                    map.set_row(t_id, &vec![F::zero(); map.domain()]);
+                }
+            }
+        }
+        map
+    }
+
+    /// Computes the primitives as the kernel of the reduced coaction in every positive grade.
+    ///
+    /// The leading basis element of the `i`-th primitive gets `primitive = Some(i)`,
+    /// this is the `h_i` of the structure lines.
     pub fn set_primitives(&mut self) {
+        let primitives: BTreeMap<G, M> = self
+            .space
+            .0
+            .keys()
+            .filter(|g| **g != G::zero())
+            .map(|g| {
+                // The tensor product is a lot bigger than the grade itself,
+                // so only keep a basis of the rows before taking the kernel
+                let reduced = self.reduced_coaction(g);
+                let mut echelon = EchelonForm::new(reduced.domain());
+                let rows: Vec<Vec<F>> = (0..reduced.codomain())
+                    .map(|t_id| reduced.get_row(t_id))
+                    .filter(|row| echelon.push_row(row))
+                    .map(|row| row.to_vec())
+                    .collect();
+                let mut compressed = M::zero(reduced.domain(), rows.len());
+                for (t_id, row) in rows.iter().enumerate() {
+                    compressed.set_row(t_id, row);
+                }
+                (*g, compressed.kernel())
+            })
+            .collect();
+
         let mut primitive_index = 0;
+        for (grade, basis_elements) in self.space.0.iter_mut() {
+            basis_elements.iter_mut().for_each(|el| el.primitive = None);
+            if let Some(kernel) = primitives.get(grade) {
+                for (column, row) in kernel.pivots() {
+                    basis_elements[column].primitive = Some(primitive_index + row);
+                }
+                primitive_index += kernel.codomain();
+            }
+        }
+        self.primitives = GradedLinearMap::from(primitives);
+    }
 
-        for (grade, basis_elements) in self.space.0.iter_mut().sorted_by_key(|(g, _)| *g) {
-            let coact_map = &self.coaction.maps[grade];
-            for (index, el) in basis_elements.iter_mut().enumerate() {
-                let mut non_zero_count = 0;
+    /// Leading basis elements of the primitives, in the order of their `h_i`.
+    ///
+    /// The primitives are in reduced row echelon form, so the duals of these elements
+    /// form the dual basis of the indecomposables of the dual algebra.
+    pub fn indecomposables(&self) -> Vec<BasisIndex<G>> {
+        self.primitives
+            .maps
+            .iter()
+            .flat_map(|(g, kernel)| kernel.pivots().into_iter().map(|(column, _)| (*g, column)))
+            .collect()
+    }
 
-                // Count non-zero entries
-                for t_id in 0..coact_map.codomain() {
-                    if !coact_map.get(index, t_id).is_zero() {
-                        non_zero_count += 1;
-                    }
+    /// Product of two basis elements in the basis of its grade,
+    /// `None` without a product or when the grade is past the limit
+    pub fn multiply(&self, left: BasisIndex<G>, right: BasisIndex<G>) -> Option<(G, Vec<F>)> {
+        let product = self.product.as_ref()?;
+        let &(t_gr, t_id) = self.tensor.construct.get(&right)?.get(&left)?;
+        let map = product.maps.get(&t_gr)?;
+        Some((
+            t_gr,
+            (0..map.codomain()).map(|i| map.get(t_id, i)).collect(),
+        ))
+    }
+
+    /// Antipode `χ`, found recursively from `Σ x' χ(x'') = 0` for `x` in positive grade,
+    /// so `χ(x) = -x - Σ x' χ(x'')` over the reduced coaction.
+    pub fn antipode(&self) -> Result<GradedLinearMap<G, F, M>, String> {
+        if self.product.is_none() {
+            return Err("Coalgebra has no product to compute the antipode with".to_owned());
+        }
+        if self.space.0.get(&G::zero()).map(|els| els.len()) != Some(1) {
+            return Err("Antipode can only be computed for a connected coalgebra".to_owned());
+        }
+
+        let mut values: HashMap<BasisIndex<G>, Vec<F>, RandomState> = HashMap::default();
+        for (grade, els) in &self.space.0 {
+            for index in 0..els.len() {
+                self.antipode_of((*grade, index), &mut values, &mut vec![])?;
+            }
+        }
+
+        let maps: BTreeMap<G, M> = self
+            .space
+            .0
+            .iter()
+            .map(|(grade, els)| {
+                let mut map = M::zero(els.len(), els.len());
+                for index in 0..els.len() {
+                    map.set_row(index, &values[&(*grade, index)]);
                 }
+                (*grade, map.transpose())
+            })
+            .collect();
+        Ok(GradedLinearMap::from(maps))
+    }
 
-                // Check if exactly 2 non-zero entries
-                if non_zero_count == 2 {
-                    el.primitive = Some(primitive_index);
-                    primitive_index += 1;
+    fn antipode_of(
+        &self,
+        x: BasisIndex<G>,
+        values: &mut HashMap<BasisIndex<G>, Vec<F>, RandomState>,
+        visiting: &mut Vec<BasisIndex<G>>,
+    ) -> Result<(), String> {
+        if values.contains_key(&x) {
+            return Ok(());
+        }
+        let unit = (G::zero(), 0);
+        let mut value = vec![F::zero(); self.space.dimension_in_grade(&x.0)];
+        if x == unit {
+            value[0] = F::one();
+            values.insert(x, value);
+            return Ok(());
+        }
+        if visiting.contains(&x) {
+            return Err(format!(
+                "Antipode of '{}' depends on itself",
+                self.space.0[&x.0][x.1].name
+            ));
+        }
+        visiting.push(x);
+
+        for (a, b, c) in terms(&self.coaction, &self.tensor, x) {
+            if a == unit {
+                continue;
+            }
+            self.antipode_of(b, values, visiting)?;
+            for (k, coeff) in values[&b].clone().into_iter().enumerate() {
+                if coeff.is_zero() {
+                    continue;
+                }
+                let (_, product) = self.multiply(a, (b.0, k)).ok_or(format!(
+                    "Product of '{}' and '{}' is not known",
+                    self.space.0[&a.0][a.1].name, self.space.0[&b.0][k].name
+                ))?;
+                for (v, p) in value.iter_mut().zip(product) {
+                    *v -= c * coeff * p;
                 }
             }
         }
+
+        visiting.pop();
+        values.insert(x, value);
+        Ok(())
     }
 
     pub fn set_generator(&mut self) -> Result<(), &str> {
@@ -92,7 +233,7 @@ pub fn A0_coalgebra() -> kCoalgebra<UniGrading, F2, RowMatrix<F2>> {
         }],
     );
 
-    let mut dimensions = HashMap::default();
+    let mut dimensions = BTreeMap::new();
     dimensions.insert(0, 1);
     dimensions.insert(1, 2);
 
@@ -136,11 +277,34 @@ pub fn A0_coalgebra() -> kCoalgebra<UniGrading, F2, RowMatrix<F2>> {
         },
     );
 
-    kCoalgebra {
+    // xi1^2 = 0, only the products with the unit remain
+    let mut product = GradedLinearMap::empty();
+    product.maps.insert(
+        0,
+        RowMatrix {
+            data: vec![vec![F2::one()]],
+            domain: 1,
+            codomain: 1,
+        },
+    );
+    product.maps.insert(
+        1,
+        RowMatrix {
+            data: vec![vec![F2::one(), F2::one()]],
+            domain: 2,
+            codomain: 1,
+        },
+    );
+
+    let mut coalgebra = kCoalgebra {
         space,
         coaction,
         tensor,
-    }
+        primitives: GradedLinearMap::empty(),
+        product: Some(product),
+    };
+    coalgebra.set_primitives();
+    coalgebra
 }
 
 pub fn reduce_helper<G: Grading, F: Field, M: Matrix<F>>(
@@ -182,7 +346,7 @@ pub fn reduce_helper<G: Grading, F: Field, M: Matrix<F>>(
             for (old_row, new_maybe) in mapping.get(grade).unwrap().0.iter().enumerate() {
                 match new_maybe {
                     Some(new_row) => {
-                        new_matrix.set_row(*new_row, matrix.get_row(old_row));
+                        new_matrix.set_row(*new_row, &matrix.get_row(old_row));
                     }
                     None => {}
                 }
@@ -1,6 +1,8 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
-use ahash::RandomState;
 use serde::{Deserialize, Serialize};
 
 use crate::linalg::{
@@ -89,7 +91,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
         temp_coac
             .maps
             .iter()
-            .filter(|(&gr, _)| gr <= limit)
+            .filter(|(&gr, _)| gr.within(limit))
             .map(|(_, map)| {
                 let kernel = map.kernel();
                 kernel.codomain()
@@ -139,11 +141,10 @@ impl<G: Grading, F: Field, M: Matrix<F>> Comodule<G> for kComodule<G, F, M> {
             generated_index: 0,
         };
 
-        let space_map: HashMap<G, Vec<kBasisElement>, RandomState> =
-            [(zero, vec![el])].into_iter().collect();
+        let space_map: BTreeMap<G, Vec<kBasisElement>> = [(zero, vec![el])].into_iter().collect();
         let space = GradedVectorSpace::from(space_map);
 
-        let coact_map: HashMap<G, M, RandomState> = [(zero, M::identity(1))].into_iter().collect();
+        let coact_map: BTreeMap<G, M> = [(zero, M::identity(1))].into_iter().collect();
         let coaction = GradedLinearMap::from(coact_map);
 
         debug_assert_eq!(
@@ -157,7 +158,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> Comodule<G> for kComodule<G, F, M> {
             "Coalgebra is not a connected coalgebra"
         );
 
-        let mut dimensions = HashMap::default();
+        let mut dimensions = BTreeMap::new();
         dimensions.insert(zero, 1);
 
         let mut construct = HashMap::default();
@@ -203,13 +204,13 @@ impl<G: Grading, F: Field, M: Matrix<F>> Comodule<G> for kComodule<G, F, M> {
     }
 
     fn cofree_comodule(coalgebra: Arc<Self::Coalgebra>, index: usize, grade: G, limit: G) -> Self {
-        let coaction: HashMap<G, M, RandomState> = coalgebra
+        let coaction: BTreeMap<G, M> = coalgebra
             .coaction
             .maps
             .iter()
             .filter_map(|(g, v)| {
                 let sum = *g + grade;
-                if sum <= limit {
+                if sum.within(limit) {
                     Some((*g + grade, v.clone()))
                 } else {
                     None
@@ -222,7 +223,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> Comodule<G> for kComodule<G, F, M> {
             .iter()
             .filter_map(|(g, v)| {
                 let sum = *g + grade;
-                if sum <= limit {
+                if sum.within(limit) {
                     let k_basis: Vec<kBasisElement> = v
                         .iter()
                         .map(|basis| {
@@ -1,15 +1,18 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 use ahash::RandomState;
-use itertools::Itertools;
 use rayon::prelude::*;
 
 use crate::{
     comodule::{kcomodule::kBasisElement, ktensor::kTensor, traits::Tensor},
     linalg::{
+        echelon::EchelonForm,
         field::Field,
-        graded::{BasisIndex, GradedLinearMap},
-        grading::Grading,
+        graded::{BasisIndex, GradedLinearMap, GradedVectorSpace},
+        grading::{Grading, LimitPolicy},
         matrix::Matrix,
     },
 };
@@ -74,9 +77,27 @@ impl<G: Grading, F: Field, M: Matrix<F>> ComoduleMorphism<G, kComodule<G, F, M>>
     for kComoduleMorphism<G, F, M>
 {
     fn cokernel(&self) -> Self {
-        let cokernel_map = self.map.get_cokernel();
-
-        let coker_space = cokernel_map.codomain_space(kBasisElement::default());
+        let quotient = self.map.cokernel_quotient(&self.codomain.space);
+        let cokernel_map = quotient.projection;
+
+        // Only the names of the surviving elements are kept, the rest is recomputed
+        let coker_space = GradedVectorSpace::from(
+            quotient
+                .space
+                .0
+                .into_iter()
+                .map(|(g, els)| {
+                    let els = els
+                        .into_iter()
+                        .map(|el| kBasisElement {
+                            name: el.name,
+                            ..Default::default()
+                        })
+                        .collect();
+                    (g, els)
+                })
+                .collect::<BTreeMap<_, _>>(),
+        );
 
         let coalg = self.codomain.coalgebra.as_ref();
         let tensor = kTensor::generate(&coalg.space, &coker_space);
@@ -109,7 +130,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> ComoduleMorphism<G, kComodule<G, F, M>>
             })
             .collect();
 
-        let coaction: HashMap<G, M, RandomState> = coker_space
+        let coaction: BTreeMap<G, M> = coker_space
             .0
             .par_iter()
             .map(|(g, v)| {
@@ -152,7 +173,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> ComoduleMorphism<G, kComodule<G, F, M>>
         Self::new(self.codomain.clone(), Arc::new(comodule), cokernel_map)
     }
 
-    fn inject_codomain_to_cofree(&self, limit: G) -> Self {
+    fn inject_codomain_to_cofree(&self, limit: G, policy: LimitPolicy<G>) -> Self {
         let mut growing_map: GradedLinearMap<G, F, M> =
             GradedLinearMap::zero_codomain(&self.codomain.space);
         let mut growing_comodule = kComodule::zero_comodule(self.codomain.coalgebra.clone());
@@ -160,33 +181,34 @@ impl<G: Grading, F: Field, M: Matrix<F>> ComoduleMorphism<G, kComodule<G, F, M>>
 
         let grades: Vec<G> = growing_map
             .maps
-            .iter()
-            .map(|(g, _)| *g)
-            .sorted()
-            .filter(|&g| g <= limit)
+            .keys()
+            .copied()
+            .filter(|&g| g.within(limit))
             .collect();
         let mut prev_grade = 0;
 
-        let fixed_limit = limit.incr().incr();
+        // Row echelon form of growing_map per grade, updated as cofree blocks get stacked
+        let mut echelons: HashMap<G, EchelonForm<F>, RandomState> = grades
+            .iter()
+            .map(|g| {
+                (
+                    *g,
+                    EchelonForm::new(self.codomain.space.dimension_in_grade(g)),
+                )
+            })
+            .collect();
+
+        let fixed_limit = policy.padded(limit);
 
         loop {
             // Get lowest graded pivot element
             let mut pivot = None;
             for grade_id in prev_grade..grades.len() {
                 let grade = grades[grade_id];
-                let kernel = growing_map
-                    .maps
-                    .get(&grade)
-                    .expect("This should exist")
-                    .kernel();
-
-                match kernel.first_non_zero_entry() {
-                    Some(loc) => {
-                        prev_grade = grade_id;
-                        pivot = Some((loc, grade));
-                        break;
-                    }
-                    None => {}
+                if let Some(col) = echelons[&grade].kernel_lead() {
+                    prev_grade = grade_id;
+                    pivot = Some((col, grade));
+                    break;
                 }
             }
             let (pivot, pivot_grade) = match pivot {
@@ -200,16 +222,16 @@ impl<G: Grading, F: Field, M: Matrix<F>> ComoduleMorphism<G, kComodule<G, F, M>>
                 .codomain
                 .tensor
                 .construct
-                .get(&(pivot_grade, pivot.1))
+                .get(&(pivot_grade, pivot))
                 .expect("The tensor should exist on the codomain in this grade");
 
             let coalg_space = &self.codomain.coalgebra.space;
 
             // TODO: Verify is this parallel iterator is faster or not for big(ger) coalgebras
-            let cofree_map: HashMap<G,M, RandomState> = coalg_space.0.iter().filter_map(|(alg_gr, alg_gr_space)| {
+            let cofree_map: BTreeMap<G, M> = coalg_space.0.iter().filter_map(|(alg_gr, alg_gr_space)| {
                 let t_gr = *alg_gr + pivot_grade;
 
-                if t_gr > fixed_limit {
+                if !t_gr.within(fixed_limit) {
                     return None;
                 }
 
@@ -232,7 +254,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> ComoduleMorphism<G, kComodule<G, F, M>>
                         .get(t_gr)
                         .expect("This grade should exist on the coaction of the injecting comodule")
                         .get_row(*t_id);
-                    map.set_row(a_id, slice);
+                    map.set_row(a_id, &slice);
                 }
 
                 Some((t_gr, map))
@@ -245,6 +267,14 @@ impl<G: Grading, F: Field, M: Matrix<F>> ComoduleMorphism<G, kComodule<G, F, M>>
                 fixed_limit,
             );
 
+            for (gr, map) in cofree_map.iter() {
+                if let Some(echelon) = echelons.get_mut(gr) {
+                    for row in 0..map.codomain() {
+                        echelon.push_row(&map.get_row(row));
+                    }
+                }
+            }
+
             growing_comodule.direct_sum(&mut f);
             growing_map.vstack(&mut GradedLinearMap::from(cofree_map));
 
@@ -290,30 +320,65 @@ impl<G: Grading, F: Field, M: Matrix<F>> ComoduleMorphism<G, kComodule<G, F, M>>
         Self::new(domain, codomain, map)
     }
 
-    fn get_structure_lines(&self) -> Vec<(usize, usize, usize, String)> {
-        let mut lines = vec![];
+    fn get_structure_lines(&self) -> Vec<(usize, usize, String, String)> {
+        let coalgebra = &self.domain.coalgebra;
+        let generators: HashMap<usize, G, RandomState> = self
+            .domain
+            .get_generators()
+            .into_iter()
+            .map(|(index, g, _)| (index, g))
+            .collect();
 
+        // Index of the first h_i in every grade of the coalgebra
+        let mut offsets = HashMap::<G, usize, RandomState>::default();
+        let mut offset = 0;
+        for (g, primitives) in &coalgebra.primitives.maps {
+            offsets.insert(*g, offset);
+            offset += primitives.codomain();
+        }
+
+        let mut lines = vec![];
         for (gr, gr_map) in self.map.maps.iter() {
-            for el_id in 0..self.domain.space.dimension_in_grade(gr) {
-                let els = self.domain.space.0.get(gr).unwrap();
-                match els[el_id].primitive {
-                    Some(prim_id) => {
-                        for t_id in 0..gr_map.codomain() {
-                            let t_el = &self.codomain.space.0.get(gr).expect("As codomain of the map is non-zero this vector space should contain an element in this grade.")[t_id];
-                            if t_el.generator {
-                                if !gr_map.get(el_id, t_id).is_zero() {
-                                    lines.push((
-                                        els[el_id].generated_index,
-                                        t_el.generated_index,
-                                        gr_map.get(el_id, t_id).as_usize(),
-                                        "h_".to_string() + &prim_id.to_string(),
-                                    ));
-                                }
+            let Some(els) = self.domain.space.0.get(gr) else {
+                continue;
+            };
+
+            // The elements of a cofree summand are next to each other, in the order of the coalgebra basis
+            let mut start = 0;
+            while start < els.len() {
+                let index = els[start].generated_index;
+                let len = els[start..]
+                    .iter()
+                    .take_while(|el| el.generated_index == index)
+                    .count();
+                let alg_gr = generators.get(&index).map(|g| *gr - *g);
+
+                if let Some((alg_gr, primitives)) =
+                    alg_gr.and_then(|a| Some((a, coalgebra.primitives.maps.get(&a)?)))
+                {
+                    debug_assert_eq!(primitives.domain(), len);
+                    for t_id in 0..gr_map.codomain() {
+                        let t_el = &self.codomain.space.0.get(gr).expect("As codomain of the map is non-zero this vector space should contain an element in this grade.")[t_id];
+                        if !t_el.generator {
+                            continue;
+                        }
+                        // Pair the functional the map gives on this summand with every primitive
+                        for q in 0..primitives.codomain() {
+                            let val: F = (0..len)
+                                .map(|k| gr_map.get(start + k, t_id) * primitives.get(k, q))
+                                .sum();
+                            if !val.is_zero() {
+                                lines.push((
+                                    index,
+                                    t_el.generated_index,
+                                    val.export_value(),
+                                    "h_".to_string() + &(offsets[&alg_gr] + q).to_string(),
+                                ));
                             }
                         }
                     }
-                    None => {}
                 }
+                start += len;
             }
         }
 
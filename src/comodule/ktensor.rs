@@ -1,7 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use ahash::RandomState;
-use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use crate::linalg::{
@@ -14,7 +13,7 @@ use super::traits::Tensor;
 pub type TensorConstruct<G> =
     HashMap<BasisIndex<G>, HashMap<BasisIndex<G>, BasisIndex<G>, RandomState>, RandomState>;
 pub type TensorDeconstruct<G> = HashMap<BasisIndex<G>, (BasisIndex<G>, BasisIndex<G>), RandomState>;
-pub type TensorDimension<G> = HashMap<G, usize, RandomState>;
+pub type TensorDimension<G> = BTreeMap<G, usize>;
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[allow(non_camel_case_types)]
@@ -63,10 +62,10 @@ impl<G: Grading> kTensor<G> {
     ) -> Self {
         let mut construct = HashMap::default();
         let mut deconstruct = HashMap::default();
-        let mut dimensions = HashMap::default();
+        let mut dimensions = BTreeMap::new();
 
-        // This sorted is important for consistency !
-        for (l_grade, l_elements) in left.0.iter().sorted_by_key(|(&lg, _)| lg) {
+        // Grades are iterated in order, so the tensor indices are the same on every run
+        for (l_grade, l_elements) in left.0.iter() {
             for l_id in 0..l_elements.len() {
                 for (r_grade, r_elements) in right.0.iter() {
                     let t_grade = *l_grade + *r_grade;
@@ -105,7 +104,7 @@ impl<G: Grading> kTensor<G> {
             .iter()
             .filter_map(|((m_gr, m_id), map)| {
                 let m_sum = *m_gr + add;
-                if m_sum > limit {
+                if !m_sum.within(limit) {
                     return None;
                 }
 
@@ -113,7 +112,7 @@ impl<G: Grading> kTensor<G> {
                     .iter()
                     .filter_map(|((a_gr, a_id), (t_gr, t_id))| {
                         let t_sum = *t_gr + add;
-                        if t_sum <= limit {
+                        if t_sum.within(limit) {
                             Some(((*a_gr, *a_id), (t_sum, *t_id)))
                         } else {
                             None
@@ -131,7 +130,7 @@ impl<G: Grading> kTensor<G> {
             .filter_map(|((t_gr, t_id), ((a_gr, a_id), (m_gr, m_id)))| {
                 let t_sum = *t_gr + add;
                 let m_sum = *m_gr + add;
-                if t_sum <= limit {
+                if t_sum.within(limit) {
                     Some(((t_sum, *t_id), ((*a_gr, *a_id), (m_sum, *m_id))))
                 } else {
                     None
@@ -144,7 +143,7 @@ impl<G: Grading> kTensor<G> {
             .iter()
             .filter_map(|(t_gr, size)| {
                 let t_sum = *t_gr + add;
-                if t_sum <= limit {
+                if t_sum.within(limit) {
                     Some((t_sum, *size))
                 } else {
                     None
@@ -165,7 +164,7 @@ impl<G: Grading> kTensor<G> {
     /// We should expect that direct summing the underlying vector spaces creates the correct new tensor object
     ///
     /// After careful thinking, this direct sum is not dependent on the non-determinism of the hashmap
-    pub fn direct_sum(&mut self, other: &mut Self, self_space_dimensions: &HashMap<G, usize>) {
+    pub fn direct_sum(&mut self, other: &mut Self, self_space_dimensions: &BTreeMap<G, usize>) {
         // TODO: Consider using parallel here for bigger coalgebras ?
         self.construct
             .extend(other.construct.drain().map(|((m_gr, m_id), maps)| {
@@ -221,7 +220,7 @@ impl<G: Grading> kTensor<G> {
                 })
         });
 
-        let mut dims = HashMap::default();
+        let mut dims = BTreeMap::new();
 
         let mut found: HashMap<G, Vec<bool>, RandomState> = self
             .dimensions
@@ -3,7 +3,9 @@ pub mod kcomodule;
 pub mod kmorphism;
 pub mod ktensor;
 pub mod parsers;
+pub mod steenrod;
 pub mod traits;
+pub mod validate;
 
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,7 @@
-use std::{collections::HashMap, ops::AddAssign, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 use ahash::RandomState;
 use itertools::Itertools;
@@ -6,7 +9,7 @@ use itertools::Itertools;
 use crate::linalg::{
     field::Field,
     graded::{BasisIndex, GradedLinearMap, GradedVectorSpace},
-    grading::Grading,
+    grading::{Grading, LimitPolicy},
     matrix::Matrix,
 };
 
@@ -14,8 +17,29 @@ use super::{
     kcoalgebra::kCoalgebra,
     kcomodule::{kBasisElement, kComodule},
     ktensor::kTensor,
+    validate::diagnostics_to_string,
 };
 
+/// Options for parsing coalgebras and comodules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions<G: Grading> {
+    /// Check the axioms with `validate` and fail with its diagnostics,
+    /// coalgebras with a product are checked with `validate_hopf` as well
+    pub validate: bool,
+    /// Policy of the resolution the result is used in, polynomial input is
+    /// built up to `policy.padded(max_grading)`
+    pub policy: LimitPolicy<G>,
+}
+
+impl<G: Grading> Default for ParseOptions<G> {
+    fn default() -> Self {
+        Self {
+            validate: false,
+            policy: LimitPolicy::default(),
+        }
+    }
+}
+
 impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
     fn check_translator(&self, translate: &HashMap<String, BasisIndex<G>, RandomState>) -> bool {
         for (gr, space) in &self.space.0 {
@@ -43,11 +67,29 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
         ),
         String,
     > {
-        if input.contains("- BASIS") {
+        Self::parse_with_options(input, max_grading, ParseOptions::default())
+    }
+
+    pub fn parse_with_options(
+        input: &str,
+        max_grading: G,
+        options: ParseOptions<G>,
+    ) -> Result<ParsedCoalgebra<G, F, M>, String> {
+        let parsed = if input.contains("- BASIS") {
             Self::parse_direct(input)
         } else {
-            Self::parse_polynomial_hopf_algebra(input, max_grading)
+            Self::parse_polynomial_hopf_algebra(input, max_grading, options.policy)
+        }?;
+        if options.validate {
+            let mut diagnostics = parsed.0.validate();
+            if diagnostics.is_empty() && parsed.0.product.is_some() {
+                diagnostics = parsed.0.validate_hopf();
+            }
+            if !diagnostics.is_empty() {
+                return Err(diagnostics_to_string(&diagnostics));
+            }
         }
+        Ok(parsed)
     }
 
     fn parse_direct(
@@ -65,11 +107,15 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
             Field,
             Basis,
             Coaction,
+            Product,
         }
         let mut state = State::None;
-        let mut field: Option<i32> = None;
+        let mut field: Option<usize> = None;
         let mut basis: Vec<(String, G)> = vec![];
         let mut coaction_lut = vec![];
+        // left, right and the terms of the product as (scalar, name)
+        type ProductLine = (String, String, Vec<(String, String)>);
+        let mut product_lut: Option<Vec<ProductLine>> = None;
 
         for (line_num, line) in input.lines().enumerate() {
             let line_num = line_num + 1;
@@ -103,21 +149,53 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
                     }
                     state = State::Coaction;
                 }
+                _ if line.starts_with("- PRODUCT") => {
+                    if state != State::Coaction {
+                        return Err(format!(
+                            "Line {}: Expected COACTION to be parsed first",
+                            line_num
+                        ));
+                    }
+                    product_lut = Some(vec![]);
+                    state = State::Product;
+                }
                 _ => match state {
                     State::Field => {
                         if field.is_some() {
                             return Err(format!("Line {}: Field already parsed", line_num));
                         }
-                        field = Some(line.parse::<i32>().map_err(|_| {
+                        field = Some(line.parse::<usize>().map_err(|_| {
                             format!("Line {}: Invalid field value '{}'", line_num, line)
                         })?);
-                        if field.unwrap() as usize != F::get_characteristic() {
+                        if field.unwrap() != F::get_order() {
                             return Err(format!(
-                                "Line {}: Field does not have the expected characteristic",
+                                "Line {}: Field does not have the expected order",
                                 line_num
                             ));
                         }
                     }
+                    State::Product => {
+                        let (factors, terms) = line.split_once(":").ok_or(format!(
+                            "Line {}: Invalid PRODUCT format '{}' - expected 'left|right:terms'",
+                            line_num, line
+                        ))?;
+                        let (l, r) = factors.split_once('|').ok_or(format!(
+                            "Line {}: Invalid product '{}' - expected 'left|right'",
+                            line_num, factors
+                        ))?;
+                        let terms = terms
+                            .split('+')
+                            .map(|t| match t.trim().split_once('.') {
+                                Some((s, t)) => (s.trim().to_string(), t.trim().to_string()),
+                                None => ("1".to_string(), t.trim().to_string()),
+                            })
+                            .collect();
+                        product_lut.get_or_insert_with(Vec::new).push((
+                            l.trim().to_string(),
+                            r.trim().to_string(),
+                            terms,
+                        ));
+                    }
                     State::Basis => {
                         let (name, grade) = line.split_once(":").ok_or(format!(
                             "Line {}: Invalid BASIS format '{}' - expected 'name:grade'",
@@ -165,7 +243,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
         }
 
         // Verify state
-        if state != State::Coaction {
+        if state != State::Coaction && state != State::Product {
             return Err("Coalgebra definition is not complete - missing sections".to_owned());
         }
 
@@ -190,7 +268,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
         }
 
         // Transform basis
-        let mut transformed = HashMap::default();
+        let mut transformed = BTreeMap::new();
         let mut basis_translate = HashMap::default();
 
         for (name, (el, gr)) in basis_dict.iter().sorted_by_key(|(name, _)| *name) {
@@ -201,7 +279,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
         let graded_space = GradedVectorSpace(transformed);
         let tensor = kTensor::generate(&graded_space, &graded_space);
 
-        let mut coaction: HashMap<G, M, RandomState> = HashMap::default();
+        let mut coaction: BTreeMap<G, M> = BTreeMap::new();
         for (gr, elements) in &graded_space.0 {
             let domain = elements.len();
             let codomain = *tensor.dimensions.get(gr).unwrap_or(&0);
@@ -248,10 +326,71 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
             }
         }
 
+        // Products with the unit are there already, the rest is zero unless it is listed
+        let product = match product_lut {
+            Some(product_lut) => {
+                let mut product: BTreeMap<G, M> = graded_space
+                    .0
+                    .iter()
+                    .map(|(gr, els)| {
+                        (
+                            *gr,
+                            M::zero(*tensor.dimensions.get(gr).unwrap_or(&0), els.len()),
+                        )
+                    })
+                    .collect();
+                let unit = (G::zero(), 0);
+                for (gr, els) in &graded_space.0 {
+                    for id in 0..els.len() {
+                        for (l_id, r_id) in [(unit, (*gr, id)), ((*gr, id), unit)] {
+                            let (_, t_id) = tensor.construct[&r_id][&l_id];
+                            product.get_mut(gr).unwrap().set(t_id, id, F::one());
+                        }
+                    }
+                }
+
+                for (l, r, terms) in product_lut {
+                    let find = |name: &String| {
+                        basis_translate.get(name).ok_or(format!(
+                            "Element '{}' not found in basis for the product of '{}' and '{}'",
+                            name, l, r
+                        ))
+                    };
+                    let (l_id, r_id) = (find(&l)?, find(&r)?);
+                    let Some(&(t_gr, t_id)) = tensor.construct[r_id].get(l_id) else {
+                        return Err(format!(
+                            "Product of '{}' and '{}' is past the largest grade",
+                            l, r
+                        ));
+                    };
+                    for (scalar, name) in terms {
+                        let (gr, id) = find(&name)?;
+                        if *gr != t_gr {
+                            return Err(format!(
+                                "Grades are not homogenous for the product of '{}' and '{}': {} != {}",
+                                l, r, gr, t_gr
+                            ));
+                        }
+                        let scalar = F::parse(&scalar).map_err(|e| {
+                            format!(
+                                "Invalid scalar '{}' for the product of '{}' and '{}': {}",
+                                scalar, l, r, e
+                            )
+                        })?;
+                        product.get_mut(gr).unwrap().set(t_id, *id, scalar);
+                    }
+                }
+                Some(GradedLinearMap::from(product))
+            }
+            None => None,
+        };
+
         let mut coalg = kCoalgebra {
             space: graded_space,
             tensor: tensor,
             coaction: GradedLinearMap::from(coaction),
+            primitives: GradedLinearMap::empty(),
+            product,
         };
 
         coalg.set_primitives();
@@ -266,6 +405,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
     fn parse_polynomial_hopf_algebra(
         input: &str,
         max_grading: G,
+        policy: LimitPolicy<G>,
     ) -> Result<
         (
             kCoalgebra<G, F, M>,
@@ -282,14 +422,13 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
             Coaction,
         }
 
-        let max_grading = max_grading.incr().incr();
         let mut state = State::None;
         let mut field: Option<usize> = None;
         let mut generators: Vec<(String, G)> = vec![];
+        let mut exterior: Vec<bool> = vec![];
         let mut relations: Vec<Monomial> = vec![];
         let mut coactions: Vec<Tensor<F>> = vec![];
         let mut generator_translate: HashMap<String, usize> = HashMap::new();
-        let mut basis_translate: HashMap<String, BasisIndex<G>, RandomState> = HashMap::default();
 
         for (line_num, line) in input.lines().enumerate() {
             let line_num = line_num + 1;
@@ -343,9 +482,9 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
                         field = Some(line.parse::<usize>().map_err(|_| {
                             format!("Line {}: Invalid field value '{}'", line_num, line)
                         })?);
-                        if field.unwrap() as usize != F::get_characteristic() {
+                        if field.unwrap() != F::get_order() {
                             return Err(format!(
-                                "Line {}: Field does not have the expected characteristic",
+                                "Line {}: Field does not have the expected order",
                                 line_num
                             ));
                         }
@@ -355,6 +494,10 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
                             "Line {}: Invalid GENERATOR format '{}' - expected 'name:grade'",
                             line_num, line
                         ))?;
+                        let (grade, is_exterior) = match grade.trim().strip_suffix("exterior") {
+                            Some(grade) => (grade, true),
+                            None => (grade, false),
+                        };
                         let grade = G::parse(grade.trim()).map_err(|e| {
                             format!(
                                 "Line {}: Invalid grade '{}' - {}",
@@ -371,6 +514,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
                         }
                         generator_translate.insert(name.trim().to_string(), generators.len());
                         generators.push((name.trim().to_string(), grade));
+                        exterior.push(is_exterior);
                     }
                     State::Relations => {
                         let monomial = parse_monomial(line, &generator_translate, generators.len())
@@ -430,7 +574,100 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
             return Err("Coalgebra definition is not complete - missing sections".to_owned());
         }
 
+        PolynomialHopfAlgebra {
+            generators,
+            exterior,
+            relations,
+            coactions,
+        }
+        .build(max_grading, policy)
+    }
+}
+
+/// Polynomial Hopf algebra given by its generators, monomial relations and the coproducts of
+/// the generators. The coalgebra is built by multiplying out the coproduct of every monomial.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolynomialHopfAlgebra<G: Grading, F: Field> {
+    pub generators: Vec<(String, G)>,
+    /// Exterior generators square to zero, the signs only depend on the parity of the grades
+    pub exterior: Vec<bool>,
+    /// Monomials which are zero, given as exponents of the generators
+    pub relations: Vec<Monomial>,
+    /// Coproduct of every generator as terms `c l|r`
+    pub coactions: Vec<Tensor<F>>,
+}
+
+impl<G: Grading, F: Field> Default for PolynomialHopfAlgebra<G, F> {
+    fn default() -> Self {
+        Self {
+            generators: vec![],
+            exterior: vec![],
+            relations: vec![],
+            coactions: vec![],
+        }
+    }
+}
+
+impl<G: Grading, F: Field> PolynomialHopfAlgebra<G, F> {
+    /// Adds a generator and returns its index
+    pub fn add_generator(&mut self, name: &str, grade: G, exterior: bool) -> usize {
+        self.generators.push((name.to_owned(), grade));
+        self.exterior.push(exterior);
+        self.generators.len() - 1
+    }
+
+    pub fn add_relation(&mut self, relation: Monomial) {
+        self.relations.push(relation);
+    }
+
+    /// Appends a coproduct, the i-th call gives the coproduct of the i-th generator
+    pub fn add_coaction(&mut self, coaction: Tensor<F>) {
+        self.coactions.push(coaction);
+    }
+
+    /// Builds the coalgebra up to `policy.padded(max_grading)`, together with the index of
+    /// every monomial
+    pub fn build<M: Matrix<F>>(
+        &self,
+        max_grading: G,
+        policy: LimitPolicy<G>,
+    ) -> Result<ParsedCoalgebra<G, F, M>, String> {
+        let generators = &self.generators;
+        let exterior = &self.exterior;
+        let mut relations = self.relations.clone();
+        let coactions = &self.coactions;
+        if coactions.len() != generators.len() {
+            return Err(format!(
+                "Expected a coaction for each of the {} generators, found {}",
+                generators.len(),
+                coactions.len()
+            ));
+        }
+        if let Some(m) = relations
+            .iter()
+            .chain(coactions.iter().flatten().flat_map(|(_, l, r)| [l, r]))
+            .find(|m| m.len() != generators.len())
+        {
+            return Err(format!(
+                "Monomial {:?} does not have an exponent for each of the {} generators",
+                m,
+                generators.len()
+            ));
+        }
+        check_generator_grades(generators, exterior)?;
+
+        let max_grading = policy.padded(max_grading);
+        let mut basis_translate: HashMap<String, BasisIndex<G>, RandomState> = HashMap::default();
+
         let n = generators.len();
+        for (index, _) in exterior.iter().enumerate().filter(|(_, e)| **e) {
+            let mut square = vec![0; n];
+            square[index] = 2;
+            relations.push(square);
+        }
+        // Graded commutative, odd generators anticommute
+        let odd: Vec<bool> = generators.iter().map(|(_, g)| g.is_odd()).collect();
+
         let one_monomial: Monomial = vec![0; n]; // All exponents zero => 1
         let mut monomial_coaction: HashMap<Monomial, Tensor<F>> = HashMap::new();
         let mut queue: Vec<Monomial> = vec![one_monomial.clone()];
@@ -462,17 +699,23 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
                     // Check if `next_monomial` is already processed
                     if !monomial_coaction.contains_key(&next_monomial) {
                         // Check if the grade of the monomial is valid
-                        let next_grade = monomial_to_grade(&next_monomial, &generators);
-                        if next_grade <= max_grading {
+                        let next_grade = monomial_to_grade(&next_monomial, generators);
+                        if next_grade.within(max_grading) {
                             // Calculate the coaction for the new monomial
-                            let coaction_result = multiply_coaction_elements(
+                            let mut coaction_result = multiply_coaction_elements(
                                 monomial_coaction.get(&current_monomial).ok_or(format!(
                                     "Basis monomial '{}' could not be found in queue",
-                                    monomial_to_string(&current_monomial, &generators)
+                                    monomial_to_string(&current_monomial, generators)
                                 ))?,
                                 &coactions[generator_index],
                                 &relations,
+                                &odd,
                             );
+                            // The generator is moved into place past the odd generators after it
+                            let generator = increment_monomial(&vec![0; n], generator_index);
+                            if reorder_sign(&current_monomial, &generator, &odd) {
+                                coaction_result.iter_mut().for_each(|(c, _, _)| *c = -*c);
+                            }
 
                             // Store the result and push new state to the queue
                             monomial_coaction.insert(next_monomial.clone(), coaction_result);
@@ -484,13 +727,13 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
             }
         }
         // Construct the basis structure
-        let mut basis: HashMap<G, Vec<kBasisElement>, RandomState> = HashMap::default();
+        let mut basis: BTreeMap<G, Vec<kBasisElement>> = BTreeMap::new();
         let mut monomial_to_grade_index: HashMap<Monomial, (G, usize), RandomState> =
             HashMap::default();
 
         for monomial in monomial_coaction.keys().sorted() {
-            let grade = monomial_to_grade(monomial, &generators);
-            let label = monomial_to_string(monomial, &generators);
+            let grade = monomial_to_grade(monomial, generators);
+            let label = monomial_to_string(monomial, generators);
 
             let element = kBasisElement {
                 name: label.clone(),
@@ -514,7 +757,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
         let tensor = kTensor::generate(&coalg_vector_space, &coalg_vector_space);
 
         // Create the coaction map
-        let mut coaction: HashMap<G, M, RandomState> = HashMap::default();
+        let mut coaction: BTreeMap<G, M> = BTreeMap::new();
 
         for (grade, els) in basis.iter() {
             let tensor_rows = tensor.dimensions[grade];
@@ -527,18 +770,18 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
             let (basis_grade, basis_index) =
                 monomial_to_grade_index.get(monomial).ok_or(format!(
                     "Expected monomial '{}' to exist in lookup",
-                    monomial_to_string(monomial, &generators)
+                    monomial_to_string(monomial, generators)
                 ))?;
 
             let map = coaction.get_mut(basis_grade).ok_or(format!("Expected a coaction to exist in dimension {basis_grade}. For the element {:?}, {:?}", monomial, coaction_elements))?;
             for (coeff, a, b) in coaction_elements {
                 let a_grade_index = monomial_to_grade_index.get(a).ok_or(format!(
                     "Expected left monomial '{}' to exist when constructing coaction",
-                    monomial_to_string(a, &generators)
+                    monomial_to_string(a, generators)
                 ))?;
                 let b_grade_index = monomial_to_grade_index.get(b).ok_or(format!(
                     "Expected right monomial '{}' to exist when constructing coaction",
-                    monomial_to_string(b, &generators)
+                    monomial_to_string(b, generators)
                 ))?;
                 let (_, tensor_index) = tensor.construct[&b_grade_index][&a_grade_index];
 
@@ -546,26 +789,79 @@ impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
             }
         }
 
+        // Multiply every pair of basis monomials, products past the limit have no tensor index
+        let mut product: BTreeMap<G, M> = basis
+            .iter()
+            .map(|(grade, els)| (*grade, M::zero(tensor.dimensions[grade], els.len())))
+            .collect();
+        for (left, left_index) in &monomial_to_grade_index {
+            for (right, right_index) in &monomial_to_grade_index {
+                let Some(&(t_grade, t_id)) = tensor.construct[right_index].get(left_index) else {
+                    continue;
+                };
+                let Some(result) = multiply_monomials(left, right, &relations) else {
+                    continue;
+                };
+                let (_, index) = monomial_to_grade_index[&result];
+                let coeff = match reorder_sign(left, right, &odd) {
+                    true => -F::one(),
+                    false => F::one(),
+                };
+                product
+                    .get_mut(&t_grade)
+                    .ok_or(format!("Expected a product to exist in grade {}", t_grade))?
+                    .set(t_id, index, coeff);
+            }
+        }
+
         let mut coalg = kCoalgebra {
             space: coalg_vector_space,
             tensor: tensor,
             coaction: GradedLinearMap::from(coaction),
+            primitives: GradedLinearMap::empty(),
+            product: Some(GradedLinearMap::from(product)),
         };
 
         coalg.set_primitives();
         coalg.set_generator()?;
         coalg.reduce();
 
-        debug_assert!(Self::check_translator(&coalg, &basis_translate));
+        debug_assert!(coalg.check_translator(&basis_translate));
 
         Ok((coalg, basis_translate))
     }
 }
 
+/// Reads the value of the `- FIELD` section of a coalgebra definition,
+/// without needing to know the field at compile time.
+pub fn parse_field(input: &str) -> Result<usize, String> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(line_num, line)| (line_num + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    lines
+        .find(|(_, line)| line.starts_with("- FIELD"))
+        .ok_or("No FIELD section found".to_owned())?;
+    let (line_num, line) = lines.next().ok_or("FIELD section is empty".to_owned())?;
+    line.parse::<usize>()
+        .map_err(|_| format!("Line {}: Invalid field value '{}'", line_num, line))
+}
+
 // Helper functions
 
-type Monomial = Vec<usize>;
-type Tensor<F> = Vec<(F, Monomial, Monomial)>;
+/// Index of every basis element by name
+pub type Translator<G> = HashMap<String, BasisIndex<G>, RandomState>;
+
+/// Coalgebra together with the index of every basis element by name
+pub type ParsedCoalgebra<G, F, M> = (kCoalgebra<G, F, M>, Translator<G>);
+
+/// Exponents of the generators
+pub type Monomial = Vec<usize>;
+pub type Tensor<F> = Vec<(F, Monomial, Monomial)>;
+/// Terms `c a|m` of a comodule coaction, with `a` a basis element of the coalgebra
+type ComoduleTensor<G, F> = Vec<(F, BasisIndex<G>, Monomial)>;
 
 fn parse_name_exponent(el: &str) -> Result<(&str, usize), String> {
     let parts: Vec<&str> = el.split('^').collect();
@@ -609,6 +905,25 @@ fn parse_monomial(
     Ok(mon)
 }
 
+/// Polynomial generators have to lie above zero, otherwise the search for monomials
+/// within the limit never ends. Exterior generators square to zero and are exempt.
+fn check_generator_grades<G: Grading>(
+    generators: &[(String, G)],
+    exterior: &[bool],
+) -> Result<(), String> {
+    match generators
+        .iter()
+        .zip(exterior)
+        .find(|((_, grade), e)| !**e && !grade.is_positive())
+    {
+        Some(((name, grade), _)) => Err(format!(
+            "Generator '{}' in grade {} is not above zero, so its powers cannot be truncated",
+            name, grade
+        )),
+        None => Ok(()),
+    }
+}
+
 // monomial opertations
 fn increment_monomial(m: &Monomial, index: usize) -> Monomial {
     let mut new_monomial = m.clone();
@@ -674,10 +989,37 @@ fn multiply_monomials(a: &Monomial, b: &Monomial, relations: &Vec<Monomial>) ->
     reduce_monomial(&product, relations)
 }
 
+fn monomial_is_odd(m: &Monomial, odd: &[bool]) -> bool {
+    m.iter()
+        .zip(odd)
+        .filter(|(_, o)| **o)
+        .map(|(x, _)| x)
+        .sum::<usize>()
+        % 2
+        == 1
+}
+
+/// Whether writing the product `a b` in generator order gives a minus sign,
+/// as every odd generator of `b` has to pass the odd generators of `a` with a larger index
+fn reorder_sign(a: &Monomial, b: &Monomial, odd: &[bool]) -> bool {
+    let mut passes = 0;
+    let mut odd_after = 0;
+    for i in (0..a.len()).rev() {
+        if odd[i] {
+            passes += b[i] * odd_after;
+            odd_after += a[i];
+        }
+    }
+    passes % 2 == 1
+}
+
+/// Multiplies in the tensor product of graded commutative algebras,
+/// `(a|b)(c|d) = (-1)^{|b||c|} ac|bd`
 fn multiply_tensor_terms<F: Field>(
     a: &(F, Monomial, Monomial),
     b: &(F, Monomial, Monomial),
     relations: &Vec<Monomial>,
+    odd: &[bool],
 ) -> Option<(F, Monomial, Monomial)> {
     let (a_coeff, a_left, a_right) = a;
     let (b_coeff, b_left, b_right) = b;
@@ -685,7 +1027,13 @@ fn multiply_tensor_terms<F: Field>(
     let left_product = multiply_monomials(a_left, b_left, relations)?;
     let right_product = multiply_monomials(a_right, b_right, relations)?;
 
-    let result_coeff = *a_coeff * *b_coeff;
+    let negative = (monomial_is_odd(a_right, odd) && monomial_is_odd(b_left, odd))
+        ^ reorder_sign(a_left, b_left, odd)
+        ^ reorder_sign(a_right, b_right, odd);
+    let result_coeff = match negative {
+        true => -(*a_coeff * *b_coeff),
+        false => *a_coeff * *b_coeff,
+    };
     Some((result_coeff, left_product, right_product))
 }
 
@@ -693,12 +1041,13 @@ fn multiply_coaction_elements<F: Field>(
     a: &Tensor<F>,
     b: &Tensor<F>,
     relations: &Vec<Monomial>,
+    odd: &[bool],
 ) -> Tensor<F> {
     let mut result: Vec<(F, Monomial, Monomial)> = vec![];
 
     for x in a {
         for y in b {
-            if let Some(term) = multiply_tensor_terms(x, y, relations) {
+            if let Some(term) = multiply_tensor_terms(x, y, relations, odd) {
                 let (coeff, left, right) = term;
                 if let Some(existing) = result
                     .iter_mut()
@@ -723,11 +1072,39 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
         coalgebra_translate: &HashMap<String, BasisIndex<G>, RandomState>,
         max_grading: G,
     ) -> Result<kComodule<G, F, M>, String> {
-        if input.contains("- BASIS") {
+        Self::parse_with_options(
+            input,
+            coalgebra,
+            coalgebra_translate,
+            max_grading,
+            ParseOptions::default(),
+        )
+    }
+
+    pub fn parse_with_options(
+        input: &str,
+        coalgebra: Arc<kCoalgebra<G, F, M>>,
+        coalgebra_translate: &HashMap<String, BasisIndex<G>, RandomState>,
+        max_grading: G,
+        options: ParseOptions<G>,
+    ) -> Result<kComodule<G, F, M>, String> {
+        let comodule = if input.contains("- BASIS") {
             Self::parse_direct(input, coalgebra, coalgebra_translate)
         } else {
-            Self::parse_polynomial(input, coalgebra, coalgebra_translate, max_grading)
+            Self::parse_polynomial(
+                input,
+                coalgebra,
+                coalgebra_translate,
+                options.policy.padded(max_grading),
+            )
+        }?;
+        if options.validate {
+            let diagnostics = comodule.validate();
+            if !diagnostics.is_empty() {
+                return Err(diagnostics_to_string(&diagnostics));
+            }
         }
+        Ok(comodule)
     }
 
     fn parse_direct(
@@ -844,7 +1221,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
         }
 
         // Transform basis
-        let mut transformed = HashMap::default();
+        let mut transformed = BTreeMap::new();
         let mut basis_translate: HashMap<String, BasisIndex<G>, RandomState> = HashMap::default();
 
         for (name, (el, gr)) in basis_dict.iter().sorted_by_key(|(name, _)| *name) {
@@ -855,7 +1232,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
         let graded_space = GradedVectorSpace(transformed);
         let tensor = kTensor::generate(&coalgebra.space, &graded_space);
 
-        let mut coaction: HashMap<G, M, RandomState> = HashMap::default();
+        let mut coaction: BTreeMap<G, M> = BTreeMap::new();
         for (gr, elements) in &graded_space.0 {
             let domain = elements.len();
             let codomain = *tensor.dimensions.get(gr).unwrap_or(&0);
@@ -913,6 +1290,9 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
         ))
     }
 
+    /// Comodule algebra on the generators, which can be marked `exterior` as for
+    /// polynomial coalgebras. Exterior generators square to zero and odd generators
+    /// anticommute, with the coaction multiplied as `(a|m)(b|n) = (-1)^{|m||b|} ab|mn`.
     fn parse_polynomial(
         input: &str,
         coalgebra: Arc<kCoalgebra<G, F, M>>,
@@ -927,13 +1307,20 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
             Coaction,
         }
 
-        let max_grading = max_grading.incr().incr();
         let mut state = State::None;
         let mut generators: Vec<(String, G)> = vec![];
+        let mut exterior: Vec<bool> = vec![];
         let mut relations: Vec<Monomial> = vec![];
-        let mut coactions: Vec<Vec<(F, HashMap<String, usize>, Vec<usize>)>> = vec![];
+        let mut coactions: Vec<ComoduleTensor<G, F>> = vec![];
         let mut generator_translate: HashMap<String, usize> = HashMap::new();
 
+        if coalgebra.product.is_none() {
+            return Err("Polynomial comodules need a coalgebra with a product".to_owned());
+        }
+        let unit = *coalgebra_translate
+            .get("1")
+            .ok_or("Coalgebra translator has no unit '1'")?;
+
         for (line_num, line) in input.lines().enumerate() {
             let line_num = line_num + 1;
             let line = line.trim();
@@ -975,6 +1362,10 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
                             "Line {}: Invalid GENERATOR format '{}' - expected 'name:grade'",
                             line_num, line
                         ))?;
+                        let (grade, is_exterior) = match grade.trim().strip_suffix("exterior") {
+                            Some(grade) => (grade, true),
+                            None => (grade, false),
+                        };
                         let grade = G::parse(grade.trim()).map_err(|e| {
                             format!(
                                 "Line {}: Invalid grade '{}' - {}",
@@ -985,6 +1376,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
                         })?;
                         generator_translate.insert(name.trim().to_string(), generators.len());
                         generators.push((name.trim().to_string(), grade));
+                        exterior.push(is_exterior);
                     }
                     State::Relations => {
                         let monomial = parse_monomial(line, &generator_translate, generators.len())
@@ -998,49 +1390,64 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
                             "Line {}: Invalid COACTION format '{}' - expected 'name:tensors'",
                             line_num, line
                         ))?;
-                        let tensors: Vec<(F, HashMap<String, usize>, Monomial)> = tensors
-                            .split('+')
-                            .map::<Result<(F, HashMap<String, usize>, Vec<usize>), String>, _>(|t| {
-                                let (s,t) = match t.split_once('.') {
-                                    Some((s, t)) => {
-                                        (F::parse(s.trim()).map_err(|e| {
-                                            format!("Line {}: Scalar {s} with tensor {t} could not be parsed. {e}", line_num)
-                                        })?, t)
-                                    },
-                                    None => {
-                                        (F::one(), t)
-                                    }
+                        let mut coaction: ComoduleTensor<G, F> = vec![];
+                        for t in tensors.split('+') {
+                            let (s, t) = match t.split_once('.') {
+                                Some((s, t)) => (
+                                    F::parse(s.trim()).map_err(|e| {
+                                        format!("Line {}: Scalar {s} with tensor {t} could not be parsed. {e}", line_num)
+                                    })?,
+                                    t,
+                                ),
+                                None => (F::one(), t),
+                            };
+                            let (l, r) = t.split_once('|').ok_or(format!(
+                                "Line {}: Invalid tensor format '{}' - expected 'left|right'",
+                                line_num, t
+                            ))?;
+
+                            // Parse left side as a product in the coalgebra, factors
+                            // outside of the coalgebra make the term vanish
+                            let mut left = vec![(s, unit)];
+                            for e in l.trim().split(',') {
+                                let (name, exponent) = parse_name_exponent(e)?;
+                                if name == "1" {
+                                    continue;
+                                }
+                                let factor = match exponent {
+                                    1 => name.to_owned(),
+                                    _ => format!("{}^{}", name, exponent),
                                 };
-                                let (l, r) = t
-                                    .split_once('|')
-                                    .ok_or(format!("Line {}: Invalid tensor format '{}' - expected 'left|right'", line_num, t))?;
-
-                                // Parse left side from coalgebra
-                                let coalgebra_elements: HashMap<String, usize> = l.trim().
-                                    split(',').map(|e| {
-                                        let (name, exponent) = parse_name_exponent(e)?;
-                                        if name == "1" {
-                                            let none: Result<Option<(String, usize)>, String> = Ok(None);
-                                            return none;
-                                        }
-                                        return Ok(Some((name.to_owned(), exponent)));
-                                    }).filter_ok(|e| {
-                                        e.is_some()
-                                    }).map_ok(|e| {e.unwrap()}).try_collect()?;
-
-                                // TODO: RHS can only be monomial ?
-                                let right = parse_monomial(
-                                    r.trim(),
-                                    &generator_translate,
-                                    generators.len(),
-                                ).map_err(|e| format!("Line {}: Invalid right monomial '{}' - {}", line_num, r.trim(), e))?;                                
-                                Ok((s, coalgebra_elements, right))
-                            })
-                            .try_collect()?;
+                                left = match coalgebra_translate.get(&factor) {
+                                    Some(index) => multiply_coalgebra_terms(
+                                        &coalgebra,
+                                        &left,
+                                        &[(F::one(), *index)],
+                                    ),
+                                    None => vec![],
+                                };
+                            }
+
+                            // TODO: RHS can only be monomial ?
+                            let right =
+                                parse_monomial(r.trim(), &generator_translate, generators.len())
+                                    .map_err(|e| {
+                                        format!(
+                                            "Line {}: Invalid right monomial '{}' - {}",
+                                            line_num,
+                                            r.trim(),
+                                            e
+                                        )
+                                    })?;
+                            for (coeff, index) in left {
+                                add_comodule_term(&mut coaction, coeff, index, right.clone());
+                            }
+                        }
                         if name.trim() != generators[coactions.len()].0 {
                             return Err(format!("Line {}: Coaction for '{}' must match generator order, expected '{}'", line_num, name.trim(), generators[coactions.len()].0));
                         }
-                        coactions.push(tensors);
+                        coaction.retain(|(coeff, _, _)| !coeff.is_zero());
+                        coactions.push(coaction);
                     }
                     _ => return Err(format!("Line {}: Unexpected state", line_num)),
                 },
@@ -1050,17 +1457,23 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
         if state != State::Coaction {
             return Err("Comodule definition is not complete - missing sections".to_owned());
         }
+        check_generator_grades(&generators, &exterior)?;
 
         let n = generators.len();
+        for (index, _) in exterior.iter().enumerate().filter(|(_, e)| **e) {
+            let mut square = vec![0; n];
+            square[index] = 2;
+            relations.push(square);
+        }
+        let odd: Vec<bool> = generators.iter().map(|(_, g)| g.is_odd()).collect();
         let one_monomial: Monomial = vec![0; n];
-        let mut monomial_coaction: HashMap<Monomial, Vec<(F, HashMap<String, usize>, Monomial)>> =
-            HashMap::new();
+        let mut monomial_coaction: HashMap<Monomial, ComoduleTensor<G, F>> = HashMap::new();
         let mut queue: Vec<Monomial> = vec![one_monomial.clone()];
 
         // Initialize basis information for the unit monomial (1)
         monomial_coaction.insert(
             one_monomial.clone(),
-            vec![(F::one(), HashMap::default(), one_monomial.clone())],
+            vec![(F::one(), unit, one_monomial.clone())],
         );
 
         let mut i = 0;
@@ -1081,15 +1494,17 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
 
                     if !monomial_coaction.contains_key(&next_monomial) {
                         let next_grade = monomial_to_grade(&next_monomial, &generators);
-                        if next_grade <= max_grading {
+                        if next_grade.within(max_grading) {
                             // Calculate the coaction for the new monomial
-                            let coaction_result = multiply_comodule_coaction_elements::<F, G>(
+                            let coaction_result = multiply_comodule_coaction_elements(
+                                &coalgebra,
                                 monomial_coaction.get(&current_monomial).ok_or(format!(
                                     "Basis monomial '{}' could not be found in queue",
                                     monomial_to_string(&current_monomial, &generators)
                                 ))?,
                                 &coactions[generator_index],
                                 &relations,
+                                &odd,
                             );
 
                             monomial_coaction.insert(next_monomial.clone(), coaction_result);
@@ -1101,7 +1516,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
         }
 
         // Construct the basis structure
-        let mut basis: HashMap<G, Vec<kBasisElement>, RandomState> = HashMap::default();
+        let mut basis: BTreeMap<G, Vec<kBasisElement>> = BTreeMap::new();
         let mut monomial_to_grade_index: HashMap<Monomial, (G, usize), RandomState> =
             HashMap::default();
 
@@ -1128,7 +1543,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
         let tensor = kTensor::generate(&coalgebra.space, &comodule_vector_space);
 
         // Create the coaction map
-        let mut coaction: HashMap<G, M, RandomState> = HashMap::default();
+        let mut coaction: BTreeMap<G, M> = BTreeMap::new();
 
         for (grade, els) in basis.iter() {
             let tensor_rows = tensor.dimensions[grade];
@@ -1149,40 +1564,20 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
 
             let map = coaction.get_mut(basis_grade).ok_or(format!("Expected a coaction to exist in dimension {basis_grade}. For the element {:?}, {:?}", monomial, coaction_elements))?;
 
-            for (coeff, coalg_indices, mod_monomial) in coaction_elements {
+            for (coeff, coalg_index, mod_monomial) in coaction_elements {
                 let mod_grade_index = monomial_to_grade_index.get(mod_monomial).ok_or(format!(
                     "Expected comodule monomial '{}' to exist when constructing coaction",
                     monomial_to_string(mod_monomial, &generators)
                 ))?;
 
-                let coalg_name = match coalg_indices.len() {
-                    0 => "1",
-                    _ => &coalg_indices
-                        .iter()
-                        .sorted()
-                        .filter_map(|(name, ind)| match ind {
-                            0 => None,
-                            1 => Some(name.to_owned()),
-                            _ => Some(name.to_owned() + "^" + &ind.to_string()),
-                        })
-                        .join(","),
-                };
+                let (_, tensor_index) = tensor
+                    .construct
+                    .get(mod_grade_index)
+                    .ok_or("Module BasisIndex  not found in tensor construction.")?
+                    .get(coalg_index)
+                    .ok_or("Algebra BasisIndex not found in tensor construction")?;
 
-                match coalgebra_translate.get(coalg_name) {
-                    Some(coalg_index) => {
-                        let (_, tensor_index) = tensor
-                            .construct
-                            .get(mod_grade_index)
-                            .ok_or("Module BasisIndex  not found in tensor construction.")?
-                            .get(coalg_index)
-                            .ok_or("Algebra BasisIndex not found in tensor construction")?;
-
-                        map.set(*basis_index, *tensor_index, *coeff);
-                    }
-                    None => {
-                        continue;
-                    }
-                }
+                map.set(*basis_index, *tensor_index, *coeff);
             }
         }
 
@@ -1195,36 +1590,78 @@ impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
     }
 }
 
-fn multiply_comodule_coaction_elements<F: Field, G: Grading>(
-    a: &Vec<(F, HashMap<String, usize>, Monomial)>,
-    b: &Vec<(F, HashMap<String, usize>, Monomial)>,
-    relations: &Vec<Monomial>,
-) -> Vec<(F, HashMap<String, usize>, Monomial)> {
-    let mut result: Vec<(F, HashMap<String, usize>, Monomial)> = vec![];
+/// Adds `c a|m` to the terms, merging it with an existing term for `a|m`
+fn add_comodule_term<G: Grading, F: Field>(
+    terms: &mut ComoduleTensor<G, F>,
+    coeff: F,
+    coalg: BasisIndex<G>,
+    module: Monomial,
+) {
+    if let Some(existing) = terms
+        .iter_mut()
+        .find(|(_, a, m)| a == &coalg && m == &module)
+    {
+        existing.0 += coeff;
+    } else {
+        terms.push((coeff, coalg, module));
+    }
+}
 
-    for x in a {
-        for y in b {
-            let (a_coeff, a_coalg, a_mod) = x;
-            let (b_coeff, b_coalg, b_mod) = y;
+/// Multiplies coalgebra elements given as terms `c a` with the product of the coalgebra,
+/// products outside of the coalgebra are dropped
+fn multiply_coalgebra_terms<G: Grading, F: Field, M: Matrix<F>>(
+    coalgebra: &kCoalgebra<G, F, M>,
+    a: &[(F, BasisIndex<G>)],
+    b: &[(F, BasisIndex<G>)],
+) -> Vec<(F, BasisIndex<G>)> {
+    let mut result: Vec<(F, BasisIndex<G>)> = vec![];
+    for (a_coeff, a_index) in a {
+        for (b_coeff, b_index) in b {
+            if let Some((grade, product)) = coalgebra.multiply(*a_index, *b_index) {
+                for (index, val) in product.into_iter().enumerate() {
+                    if val.is_zero() {
+                        continue;
+                    }
+                    let coeff = *a_coeff * *b_coeff * val;
+                    match result.iter_mut().find(|(_, i)| *i == (grade, index)) {
+                        Some(existing) => existing.0 += coeff,
+                        None => result.push((coeff, (grade, index))),
+                    }
+                }
+            }
+        }
+    }
+    result.retain(|(coeff, _)| !coeff.is_zero());
+    result
+}
 
+/// Multiplies coactions in the tensor product of the coalgebra and the comodule algebra,
+/// `(a|m)(b|n) = (-1)^{|m||b|} ab|mn`
+fn multiply_comodule_coaction_elements<G: Grading, F: Field, M: Matrix<F>>(
+    coalgebra: &kCoalgebra<G, F, M>,
+    a: &ComoduleTensor<G, F>,
+    b: &ComoduleTensor<G, F>,
+    relations: &Vec<Monomial>,
+    odd: &[bool],
+) -> ComoduleTensor<G, F> {
+    let mut result: ComoduleTensor<G, F> = vec![];
+
+    for (a_coeff, a_coalg, a_mod) in a {
+        for (b_coeff, b_coalg, b_mod) in b {
             if let Some(mod_product) = multiply_monomials(a_mod, b_mod, relations) {
-                let result_coeff = *a_coeff * *b_coeff;
-
-                let mut coalg_product = a_coalg.clone();
-                for (name, exponent) in b_coalg {
-                    coalg_product
-                        .entry(name.to_owned())
-                        .and_modify(|e| e.add_assign(exponent))
-                        .or_insert(*exponent);
-                }
+                let negative = (monomial_is_odd(a_mod, odd) && b_coalg.0.is_odd())
+                    ^ reorder_sign(a_mod, b_mod, odd);
+                let coeff = match negative {
+                    true => -(*a_coeff * *b_coeff),
+                    false => *a_coeff * *b_coeff,
+                };
 
-                if let Some(existing) = result
-                    .iter_mut()
-                    .find(|(_, coalg, monom)| coalg == &coalg_product && monom == &mod_product)
-                {
-                    existing.0 += result_coeff;
-                } else {
-                    result.push((result_coeff, coalg_product, mod_product));
+                for (c, coalg_product) in multiply_coalgebra_terms(
+                    coalgebra,
+                    &[(coeff, *a_coalg)],
+                    &[(F::one(), *b_coalg)],
+                ) {
+                    add_comodule_term(&mut result, c, coalg_product, mod_product.clone());
                 }
             }
         }
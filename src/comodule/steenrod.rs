@@ -0,0 +1,235 @@
+use crate::linalg::{
+    field::Field,
+    grading::{LimitPolicy, UniGrading},
+    matrix::Matrix,
+};
+
+use super::{
+    kcoalgebra::kCoalgebra,
+    parsers::{Monomial, ParsedCoalgebra, PolynomialHopfAlgebra, Tensor},
+};
+
+/// Profile function of a quotient Hopf algebra of the dual Steenrod algebra.
+///
+/// `xi[i - 1]` is the height of `ξ_i`, `Some(h)` gives `ξ_i^{p^h} = 0` and `None` leaves it
+/// polynomial. Generators of height zero or past the end of `xi` are left out.
+/// `tau[i]` tells whether the exterior `τ_i` is there, which only happens at odd primes.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Profile {
+    pub xi: Vec<Option<u32>>,
+    pub tau: Vec<bool>,
+    /// Doubles the degrees of the `ξ_i` at `p = 2`, giving the polynomial part `F_2[ξ_i^2]`
+    pub doubled: bool,
+}
+
+impl Profile {
+    /// All of the dual Steenrod algebra up to `ξ_n` and `τ_n`
+    pub fn full(p: usize, n: usize) -> Self {
+        Self {
+            xi: vec![None; n],
+            tau: vec![p != 2; n + 1],
+            doubled: false,
+        }
+    }
+
+    /// Dual of `A(n)`, generated by `Sq^1, ..., Sq^{2^n}` or by `β, P^1, ..., P^{p^{n-1}}`
+    pub fn a(p: usize, n: usize) -> Self {
+        match p {
+            2 => Self {
+                xi: (1..=n + 1).map(|i| Some((n + 2 - i) as u32)).collect(),
+                tau: vec![],
+                doubled: false,
+            },
+            _ => Self {
+                xi: (1..=n).map(|i| Some((n + 1 - i) as u32)).collect(),
+                tau: vec![true; n + 1],
+                doubled: false,
+            },
+        }
+    }
+
+    /// Dual of the exterior algebra `E(n)` on the Milnor primitives `Q_0, ..., Q_n`
+    pub fn e(p: usize, n: usize) -> Self {
+        match p {
+            2 => Self {
+                xi: vec![Some(1); n + 1],
+                tau: vec![],
+                doubled: false,
+            },
+            _ => Self {
+                xi: vec![],
+                tau: vec![true; n + 1],
+                doubled: false,
+            },
+        }
+    }
+
+    /// Dual of `P(n)`, following `examples/polynomial/P(0).txt`.
+    ///
+    /// This is `A(n + 1)` without the `τ_i` at odd primes and `A(n + 1)` with doubled degrees at `p = 2`.
+    pub fn p(p: usize, n: usize) -> Self {
+        Self {
+            tau: vec![],
+            doubled: p == 2,
+            ..Self::a(p, n + 1)
+        }
+    }
+
+    fn xi_degree(&self, p: usize, i: usize) -> i64 {
+        let degree = p.pow(i as u32) as i64 - 1;
+        match p != 2 || self.doubled {
+            true => 2 * degree,
+            false => degree,
+        }
+    }
+
+    fn tau_degree(p: usize, i: usize) -> i64 {
+        2 * p.pow(i as u32) as i64 - 1
+    }
+
+    /// Generators with their Milnor coproducts
+    /// `Δξ_k = Σ ξ_{k-i}^{p^i}|ξ_i` and `Δτ_k = τ_k|1 + Σ ξ_{k-i}^{p^i}|τ_i`,
+    /// leaving out the generators above `max_degree` and the terms which vanish in the quotient.
+    pub fn hopf_algebra<F: Field>(
+        &self,
+        max_degree: UniGrading,
+    ) -> PolynomialHopfAlgebra<UniGrading, F> {
+        let p = F::get_characteristic();
+        let max_degree = max_degree as i64;
+
+        // Generators in order of degree, τ_{i-1} comes before ξ_i
+        let mut xi_index: Vec<Option<usize>> = vec![None];
+        let mut tau_index: Vec<Option<usize>> = vec![];
+        // (k, whether it is τ_k instead of ξ_k, degree)
+        let mut generators: Vec<(usize, bool, i64)> = vec![];
+        for i in 0..=self.xi.len().max(self.tau.len()) {
+            if p != 2 && self.tau.get(i) == Some(&true) && Self::tau_degree(p, i) <= max_degree {
+                tau_index.push(Some(generators.len()));
+                generators.push((i, true, Self::tau_degree(p, i)));
+            } else {
+                tau_index.push(None);
+            }
+            let present = i < self.xi.len() && self.xi[i] != Some(0);
+            if present && self.xi_degree(p, i + 1) <= max_degree {
+                xi_index.push(Some(generators.len()));
+                generators.push((i + 1, false, self.xi_degree(p, i + 1)));
+            } else {
+                xi_index.push(None);
+            }
+        }
+
+        let n = generators.len();
+        let monomial = |index: Option<usize>, exponent: usize| -> Monomial {
+            let mut m = vec![0; n];
+            if let Some(index) = index {
+                m[index] = exponent;
+            }
+            m
+        };
+        // ξ_k^{e}, or None if it vanishes
+        let xi_power = |k: usize, e: usize| -> Option<Monomial> {
+            match k {
+                0 => Some(monomial(None, 0)),
+                _ => {
+                    let index = xi_index.get(k).copied().flatten()?;
+                    match self.xi[k - 1] {
+                        Some(h) if e >= p.pow(h) => None,
+                        _ => Some(monomial(Some(index), e)),
+                    }
+                }
+            }
+        };
+
+        let mut algebra = PolynomialHopfAlgebra::default();
+        for (k, tau, degree) in &generators {
+            let name = match tau {
+                true => format!("tau{}", k),
+                false => format!("xi{}", k),
+            };
+            algebra.add_generator(&name, *degree as UniGrading, *tau);
+        }
+        for (k, height) in self.xi.iter().enumerate() {
+            if let (Some(index), Some(h)) = (xi_index[k + 1], height) {
+                algebra.add_relation(monomial(Some(index), p.pow(*h)));
+            }
+        }
+
+        for (k, tau, _) in &generators {
+            let right = |i: usize| match tau {
+                true => tau_index[i].map(|t| monomial(Some(t), 1)),
+                false => xi_power(i, 1),
+            };
+            let mut coaction: Tensor<F> = (0..=*k)
+                .filter_map(|i| Some((F::one(), xi_power(k - i, p.pow(i as u32))?, right(i)?)))
+                .collect();
+            if *tau {
+                coaction.push((F::one(), monomial(tau_index[*k], 1), monomial(None, 0)));
+            }
+            algebra.add_coaction(coaction);
+        }
+        algebra
+    }
+}
+
+impl<F: Field, M: Matrix<F>> kCoalgebra<UniGrading, F, M> {
+    /// Quotient of the dual Steenrod algebra at the characteristic of `F` with the given profile
+    pub fn dual_steenrod_with_profile(
+        profile: &Profile,
+        max_grading: UniGrading,
+        policy: LimitPolicy<UniGrading>,
+    ) -> Result<ParsedCoalgebra<UniGrading, F, M>, String> {
+        // Generators up to the padded limit can still show up while building
+        profile
+            .hopf_algebra(policy.padded(max_grading))
+            .build(max_grading, policy)
+    }
+
+    /// The dual Steenrod algebra at the characteristic of `F`
+    pub fn dual_steenrod(
+        max_grading: UniGrading,
+    ) -> Result<ParsedCoalgebra<UniGrading, F, M>, String> {
+        let p = F::get_characteristic();
+        let padded = LimitPolicy::default().padded(max_grading);
+        let mut n = 1;
+        while p.pow(n as u32) as i64 <= padded as i64 {
+            n += 1;
+        }
+        Self::dual_steenrod_with_profile(&Profile::full(p, n), max_grading, LimitPolicy::default())
+    }
+
+    #[allow(non_snake_case)]
+    pub fn A(
+        n: usize,
+        max_grading: UniGrading,
+    ) -> Result<ParsedCoalgebra<UniGrading, F, M>, String> {
+        Self::dual_steenrod_with_profile(
+            &Profile::a(F::get_characteristic(), n),
+            max_grading,
+            LimitPolicy::default(),
+        )
+    }
+
+    #[allow(non_snake_case)]
+    pub fn E(
+        n: usize,
+        max_grading: UniGrading,
+    ) -> Result<ParsedCoalgebra<UniGrading, F, M>, String> {
+        Self::dual_steenrod_with_profile(
+            &Profile::e(F::get_characteristic(), n),
+            max_grading,
+            LimitPolicy::default(),
+        )
+    }
+
+    #[allow(non_snake_case)]
+    pub fn P(
+        n: usize,
+        max_grading: UniGrading,
+    ) -> Result<ParsedCoalgebra<UniGrading, F, M>, String> {
+        Self::dual_steenrod_with_profile(
+            &Profile::p(F::get_characteristic(), n),
+            max_grading,
+            LimitPolicy::default(),
+        )
+    }
+}
@@ -8,6 +8,7 @@ mod tests {
         comodule::kcoalgebra::{kCoalgebra, A0_coalgebra},
         linalg::{
             field::{CRing, Fp, F2},
+            matrix::RModMorphism,
             row_matrix::RowMatrix,
         },
     };
@@ -61,6 +62,95 @@ mod tests {
         assert!(trans.len() > 3)
     }
 
+    #[test]
+    fn test_exterior_signs() {
+        let input = include_str!("../../../examples/polynomial/A(1)_p3.txt");
+        let (kcoalg, trans) = kCoalgebra::<i32, Fp<3>, RowMatrix<Fp<3>>>::parse(input, 40).unwrap();
+
+        // E(tau0, tau1) tensor F_3[xi1]/xi1^3
+        assert_eq!(trans.len(), 12);
+        assert!(!trans.contains_key("tau0^2"));
+
+        // (1|tau0)(tau1|1) = -tau1|tau0 while (tau0|1)(1|tau1) = tau0|tau1
+        let (grade, index) = trans["tau0,tau1"];
+        let entry = |l: &str, r: &str| {
+            let (_, tensor_index) = kcoalg.tensor.construct[&trans[r]][&trans[l]];
+            kcoalg.coaction.maps[&grade].get(index, tensor_index)
+        };
+        assert_eq!(entry("tau0", "tau1"), Fp::<3>::one());
+        assert_eq!(entry("tau1", "tau0"), -Fp::<3>::one());
+    }
+
+    #[test]
+    fn test_primitives() {
+        let input = include_str!("../../../examples/polynomial/A(2).txt");
+        let (kcoalg, trans) = kCoalgebra::<i32, F2, RowMatrix<F2>>::parse(input, 20).unwrap();
+        // Dual to Sq^1, Sq^2 and Sq^4
+        let names = ["xi1", "xi1^2", "xi1^4"];
+        assert_eq!(
+            kcoalg.indecomposables(),
+            names.iter().map(|n| trans[*n]).collect::<Vec<_>>()
+        );
+        for (i, name) in names.iter().enumerate() {
+            let (grade, index) = trans[*name];
+            assert_eq!(kcoalg.space.0[&grade][index].primitive, Some(i));
+        }
+
+        // Neither x nor y is primitive, but x + y is
+        let input = "- FIELD\n2\n\n- BASIS\n1: 0\na: 1\nx: 2\ny: 2\n\n- COACTION\n\
+            1: 1|1\na: 1|a + a|1\nx: 1|x + x|1 + a|a\ny: 1|y + y|1 + a|a\n";
+        let (kcoalg, trans) = kCoalgebra::<i32, F2, RowMatrix<F2>>::parse(input, 10).unwrap();
+        assert_eq!(kcoalg.indecomposables(), vec![trans["a"], trans["x"]]);
+        assert_eq!(
+            kcoalg.primitives.maps[&2].get_row(0),
+            vec![F2::one(), F2::one()]
+        );
+        let primitive = |name: &str| kcoalg.space.0[&trans[name].0][trans[name].1].primitive;
+        assert_eq!(
+            (primitive("a"), primitive("x"), primitive("y")),
+            (Some(0), Some(1), None)
+        );
+    }
+
+    #[test]
+    fn test_product_and_antipode() {
+        let input = include_str!("../../../examples/direct/A(1).txt");
+        let (kcoalg, trans) = kCoalgebra::<i32, F2, RowMatrix<F2>>::parse(input, 10).unwrap();
+        let (grade, product) = kcoalg.multiply(trans["xi1^1"], trans["xi1^2"]).unwrap();
+        assert_eq!(grade, 3);
+        assert_eq!(product[trans["xi1^3"].1], F2::one());
+        assert_eq!(product.iter().filter(|c| !c.is_zero()).count(), 1);
+
+        // χ(ξ2) = ξ2 + ξ1^3
+        let antipode = kcoalg.antipode().unwrap();
+        let chi = |x: &str, y: &str| antipode.maps[&trans[x].0].get(trans[x].1, trans[y].1);
+        assert_eq!(chi("xi1^1", "xi1^1"), F2::one());
+        assert_eq!(chi("xi2^1", "xi2^1"), F2::one());
+        assert_eq!(chi("xi2^1", "xi1^3"), F2::one());
+
+        let input = include_str!("../../../examples/polynomial/A(1).txt");
+        let (kcoalg, trans) = kCoalgebra::<i32, F2, RowMatrix<F2>>::parse(input, 10).unwrap();
+        let antipode = kcoalg.antipode().unwrap();
+        let chi = |x: &str, y: &str| antipode.maps[&trans[x].0].get(trans[x].1, trans[y].1);
+        assert_eq!(chi("xi2", "xi2"), F2::one());
+        assert_eq!(chi("xi2", "xi1^3"), F2::one());
+
+        // χ(τ1) = -τ1 + ξ1τ0 at odd primes
+        let input = include_str!("../../../examples/polynomial/A(1)_p3.txt");
+        let (kcoalg, trans) = kCoalgebra::<i32, Fp<3>, RowMatrix<Fp<3>>>::parse(input, 20).unwrap();
+        let antipode = kcoalg.antipode().unwrap();
+        let chi = |x: &str, y: &str| antipode.maps[&trans[x].0].get(trans[x].1, trans[y].1);
+        assert_eq!(chi("tau0", "tau0"), -Fp::<3>::one());
+        assert_eq!(chi("tau1", "tau1"), -Fp::<3>::one());
+        assert_eq!(chi("tau1", "tau0,xi1"), Fp::<3>::one());
+
+        // Without a PRODUCT section only the coalgebra is known
+        let input = include_str!("../../../examples/direct/A(0).txt");
+        let (kcoalg, _) = kCoalgebra::<i32, F2, RowMatrix<F2>>::parse(input, 10).unwrap();
+        assert_eq!(kcoalg.product, None);
+        assert!(kcoalg.antipode().is_err());
+    }
+
     #[test]
     fn test_poly_vs_direct_tensor() {
         let input_direct = include_str!("../../../examples/direct/A(2).txt");
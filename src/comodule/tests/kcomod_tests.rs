@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, i32, sync::Arc};
+    use std::{collections::BTreeMap, i32, sync::Arc};
 
     use crate::{
         comodule::{
@@ -10,7 +10,8 @@ mod tests {
             traits::Comodule,
         },
         linalg::{
-            field::{Fp, F2},
+            extension_field::F4,
+            field::{CRing, Fp, F2},
             flat_matrix::FlatMatrix,
             graded::{GradedLinearMap, GradedVectorSpace},
             matrix::RModMorphism,
@@ -84,7 +85,7 @@ mod tests {
     // Test for kComodule::get_generators
     #[test]
     fn test_get_generators() {
-        let mut space_map = HashMap::default();
+        let mut space_map = BTreeMap::new();
         space_map.insert(
             0,
             vec![
@@ -169,6 +170,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_f4_comod_parser() {
+        let input_coalg = include_str!("../../../examples/direct/A(0).txt");
+        let input_comod = "- BASIS\na0: 0\na1: 1\n\n- COACTION\na0: 1|a0\na1: 1|a1 + w.xi1|a0";
+
+        assert!(kCoalgebra::<i32, F4, RowMatrix<F4>>::parse(input_coalg, i32::MAX).is_err());
+
+        let input_coalg = input_coalg.replacen("2", "4", 1);
+        let (kcoalg, translator) =
+            kCoalgebra::<i32, F4, RowMatrix<F4>>::parse(&input_coalg, i32::MAX).unwrap();
+        let comod = kComodule::<i32, F4, RowMatrix<F4>>::parse(
+            input_comod,
+            Arc::new(kcoalg),
+            &translator,
+            i32::MAX,
+        )
+        .unwrap();
+
+        let coaction = &comod.coaction.maps[&1];
+        let entries: Vec<F4> = (0..coaction.codomain())
+            .map(|t| coaction.get(0, t))
+            .filter(|x| !x.is_zero())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.contains(&F4::one()));
+        assert!(entries.contains(&F4::generator()));
+    }
+
     #[test]
     fn test_gen_comod_parser() {
         let input_coalg = include_str!("../../../examples/polynomial/Test.txt");
@@ -192,6 +221,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_p3_comod_signs() {
+        let input_coalg =
+            "- FIELD\n3\n\n- GENERATOR\ntau0: 1 exterior\n\n- RELATION\n\n- COACTION\ntau0: 1|tau0 + tau0|1";
+        let input_comod =
+            "- GENERATOR\nx: 1\ny: 2\n\n- RELATION\ny^2\n\n- COACTION\nx: 1|x + tau0|1\ny: 1|y";
+
+        let (kcoalg, translator) =
+            kCoalgebra::<i32, Fp<3>, RowMatrix<Fp<3>>>::parse(input_coalg, 10).unwrap();
+        let comod = kComodule::parse(input_comod, Arc::new(kcoalg), &translator, 6).unwrap();
+        assert!(comod.validate().is_empty());
+
+        // The cross terms of (1|x + tau0|1)^2 cancel and tau0^2 = 0, so x^2 is primitive
+        let x2 = comod.space.0[&2]
+            .iter()
+            .position(|el| el.name == "x^2")
+            .unwrap();
+        let coaction = &comod.coaction.maps[&2];
+        let entries = (0..coaction.codomain())
+            .filter(|t| !coaction.get(x2, *t).is_zero())
+            .count();
+        assert_eq!(entries, 1);
+
+        // x y picks up tau0|y without a sign, as y is even
+        let xy = comod.space.0[&3]
+            .iter()
+            .position(|el| el.name == "x,y")
+            .unwrap();
+        let coaction = &comod.coaction.maps[&3];
+        let entries: Vec<Fp<3>> = (0..coaction.codomain())
+            .map(|t| coaction.get(xy, t))
+            .filter(|x| !x.is_zero())
+            .collect();
+        assert_eq!(entries, vec![Fp::one(), Fp::one()]);
+    }
+
+    #[test]
+    fn test_p3_exterior_comod() {
+        let input_coalg = include_str!("../../../examples/polynomial/A(1)_p3.txt");
+        let input_comod = "- GENERATOR\nx: 1 exterior\ny: 2\n\n- RELATION\ny^3\n\n- COACTION\nx: 1|x + tau0|1\ny: 1|y";
+
+        let (kcoalg, translator) =
+            kCoalgebra::<i32, Fp<3>, RowMatrix<Fp<3>>>::parse(input_coalg, 20).unwrap();
+        let comod = kComodule::parse(input_comod, Arc::new(kcoalg), &translator, 10).unwrap();
+        assert!(comod.validate().is_empty());
+
+        // E[x] ⊗ F_3[y]/y^3 has one basis element in each degree up to 5
+        for grade in 0..=5 {
+            assert_eq!(comod.space.0[&grade].len(), 1);
+        }
+        assert!(!comod.space.0.contains_key(&6));
+    }
+
     #[test]
     fn test_a0_comod() {
         let input = include_str!("../../../examples/polynomial/A(0).txt");
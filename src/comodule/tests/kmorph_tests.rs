@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, sync::Arc};
+    use std::{collections::BTreeMap, sync::Arc};
 
     use crate::{
         comodule::{
@@ -12,6 +12,7 @@ mod tests {
         linalg::{
             field::{CRing, F2},
             graded::{GradedLinearMap, GradedVectorSpace},
+            grading::LimitPolicy,
             matrix::RModMorphism,
             row_matrix::RowMatrix,
         },
@@ -24,7 +25,7 @@ mod tests {
 
         let morphism = kComoduleMorphism::zero_morphism(comodule);
 
-        let cofree_morphism = morphism.inject_codomain_to_cofree(5);
+        let cofree_morphism = morphism.inject_codomain_to_cofree(5, LimitPolicy::default());
 
         let comp = kComodule::cofree_comodule(coalgebra, 0, 0, 5);
 
@@ -60,7 +61,7 @@ mod tests {
         // Assertions
         assert_eq!(cokernel_morphism.domain, morphism.codomain);
 
-        let mut map = HashMap::default();
+        let mut map = BTreeMap::new();
         map.insert(0, RowMatrix::zero(1, 0));
         map.insert(1, RowMatrix::identity(1));
         let expected_map: GradedLinearMap<i32, F2, RowMatrix<F2>> = GradedLinearMap::from(map);
@@ -87,7 +88,7 @@ mod tests {
 
         assert_eq!(
             morphism.get_structure_lines(),
-            [(0, 0, 1, "h_0".to_string())]
+            [(0, 0, "1".to_string(), "h_0".to_string())]
         );
     }
 
@@ -1,7 +1,7 @@
 use crate::linalg::graded::{BasisElement, GradedVectorSpace};
 use crate::linalg::grading::Grading;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone)]
 struct MockBasisElement;
@@ -101,7 +101,7 @@ mod tests {
             &create_mock_vector_space(right_elements),
         );
 
-        let self_space_dimensions = HashMap::from([(0, 1), (1, 1)]);
+        let self_space_dimensions = BTreeMap::from([(0, 1), (1, 1)]);
         tensor1.direct_sum(&mut tensor2, &self_space_dimensions);
 
         assert_eq!(tensor1.dimensions.get(&0), Some(&2));
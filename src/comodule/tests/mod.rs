@@ -2,3 +2,5 @@ mod kcoalgebra_tests;
 mod kcomod_tests;
 mod kmorph_tests;
 mod ktensor_tests;
+mod steenrod_tests;
+mod validate_tests;
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        comodule::{kcoalgebra::kCoalgebra, steenrod::Profile},
+        linalg::{
+            field::{Fp, F2},
+            grading::LimitPolicy,
+            row_matrix::RowMatrix,
+        },
+    };
+
+    type F3 = Fp<3>;
+    type Coalgebra2 = kCoalgebra<i32, F2, RowMatrix<F2>>;
+    type Coalgebra3 = kCoalgebra<i32, F3, RowMatrix<F3>>;
+
+    #[test]
+    fn test_a_matches_examples() {
+        let examples = [
+            (0, include_str!("../../../examples/polynomial/A(0).txt")),
+            (1, include_str!("../../../examples/polynomial/A(1).txt")),
+            (2, include_str!("../../../examples/polynomial/A(2).txt")),
+            (3, include_str!("../../../examples/polynomial/A(3).txt")),
+        ];
+        for (n, input) in examples {
+            assert_eq!(
+                Coalgebra2::A(n, 30).unwrap(),
+                Coalgebra2::parse(input, 30).unwrap(),
+                "A({}) differs from the example",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_dual_steenrod_matches_example() {
+        let input = include_str!("../../../examples/polynomial/A.txt");
+        assert_eq!(
+            Coalgebra2::dual_steenrod(30).unwrap(),
+            Coalgebra2::parse(input, 30).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_p_matches_examples() {
+        // The example names the generators of the polynomial part zeta
+        let input = include_str!("../../../examples/polynomial/P(0).txt").replace("zeta", "xi");
+        assert_eq!(
+            Coalgebra2::P(0, 40).unwrap(),
+            Coalgebra2::parse(&input, 40).unwrap()
+        );
+
+        let input = include_str!("../../../examples/polynomial/P(3).txt");
+        let polynomial_part = Profile {
+            xi: vec![None; 3],
+            ..Default::default()
+        };
+        assert_eq!(
+            Coalgebra3::dual_steenrod_with_profile(&polynomial_part, 129, LimitPolicy::default())
+                .unwrap(),
+            Coalgebra3::parse(input, 129).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_odd_primes() {
+        let input = include_str!("../../../examples/polynomial/A(1)_p3.txt");
+        assert_eq!(
+            Coalgebra3::A(1, 40).unwrap(),
+            Coalgebra3::parse(input, 40).unwrap()
+        );
+
+        let input = include_str!("../../../examples/polynomial/A_p3.txt");
+        assert_eq!(
+            Coalgebra3::dual_steenrod(40).unwrap(),
+            Coalgebra3::parse(input, 40).unwrap()
+        );
+
+        // E(1) = E(τ0, τ1) and A(1) = E(τ0, τ1) ⊗ F_3[ξ1]/ξ1^3
+        let (e1, _) = Coalgebra3::E(1, 40).unwrap();
+        assert_eq!(e1.space.0.values().map(|v| v.len()).sum::<usize>(), 4);
+        let (a1, _) = Coalgebra3::A(1, 40).unwrap();
+        assert_eq!(a1.space.0.values().map(|v| v.len()).sum::<usize>(), 12);
+    }
+
+    #[test]
+    fn test_e_at_two() {
+        // E(1) = E(ξ1, ξ2)
+        let (e1, translate) = Coalgebra2::E(1, 40).unwrap();
+        assert_eq!(e1.space.0.values().map(|v| v.len()).sum::<usize>(), 4);
+        assert_eq!(translate["xi1,xi2"].0, 4);
+    }
+}
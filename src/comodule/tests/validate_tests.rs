@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        comodule::{
+            kcoalgebra::kCoalgebra, kcomodule::kComodule, parsers::ParseOptions, traits::Comodule,
+            validate::Diagnostic,
+        },
+        linalg::{
+            field::{Fp, F2},
+            grading::LimitPolicy,
+            row_matrix::RowMatrix,
+        },
+    };
+
+    type Coalgebra2 = kCoalgebra<i32, F2, RowMatrix<F2>>;
+    type Coalgebra3 = kCoalgebra<i32, Fp<3>, RowMatrix<Fp<3>>>;
+
+    const VALIDATE: ParseOptions<i32> = ParseOptions {
+        validate: true,
+        policy: LimitPolicy::Step(1),
+    };
+
+    #[test]
+    fn test_examples_validate() {
+        for input in [
+            include_str!("../../../examples/direct/A(0).txt"),
+            include_str!("../../../examples/direct/A(2).txt"),
+            include_str!("../../../examples/polynomial/A(2).txt"),
+            include_str!("../../../examples/polynomial/A.txt"),
+            include_str!("../../../examples/polynomial/X(2).txt"),
+        ] {
+            let (coalgebra, _) = Coalgebra2::parse(input, 30).unwrap();
+            assert_eq!(coalgebra.validate(), vec![]);
+        }
+
+        // Odd primes, with the signs of the odd generators
+        for input in [
+            include_str!("../../../examples/polynomial/A(1)_p3.txt"),
+            include_str!("../../../examples/polynomial/A_p3.txt"),
+        ] {
+            Coalgebra3::parse_with_options(input, 40, VALIDATE).unwrap();
+        }
+
+        // x1 is odd but not exterior, so x1^2 is not graded commutative and the
+        // coproduct is only coassociative on the generators
+        let (coalgebra, _) =
+            Coalgebra3::parse(include_str!("../../../examples/polynomial/Test.txt"), 40).unwrap();
+        assert_eq!(
+            coalgebra.validate()[0],
+            Diagnostic::NotCoassociative {
+                element: "x1^2,x2".to_owned()
+            }
+        );
+
+        let (coalgebra, translator) =
+            Coalgebra2::parse(include_str!("../../../examples/polynomial/A(0).txt"), 32).unwrap();
+        let coalgebra = Arc::new(coalgebra);
+        let input = include_str!("../../../examples/comodule/A(0)_comod.txt");
+        assert!(
+            kComodule::parse_with_options(input, coalgebra.clone(), &translator, 32, VALIDATE)
+                .is_ok()
+        );
+        let cofree = kComodule::cofree_comodule(coalgebra, 0, 1, 32);
+        assert_eq!(cofree.validate(), vec![]);
+    }
+
+    #[test]
+    fn test_coalgebra_diagnostics() {
+        // (Δ ⊗ 1)Δz misses the term x|x|x of (1 ⊗ Δ)Δz
+        let input = "- FIELD\n2\n\n- BASIS\n1: 0\nx: 1\ny: 2\nz: 3\n\n- COACTION\n\
+            1: 1|1\nx: 1|x + x|1\ny: 1|y + y|1 + x|x\nz: 1|z + z|1 + x|y\n";
+        let (coalgebra, _) = Coalgebra2::parse(input, 10).unwrap();
+        assert_eq!(
+            coalgebra.validate(),
+            vec![Diagnostic::NotCoassociative {
+                element: "z".to_owned()
+            }]
+        );
+        let err = Coalgebra2::parse_with_options(input, 10, VALIDATE).unwrap_err();
+        assert!(err.contains("'z'"));
+
+        // A typo in the coaction of y drops its right counit term
+        let input = input.replace("y: 1|y + y|1 + x|x", "y: 1|y + x|x");
+        let (coalgebra, _) = Coalgebra2::parse(&input, 10).unwrap();
+        assert!(coalgebra.validate().contains(&Diagnostic::RightCounit {
+            element: "y".to_owned()
+        }));
+
+        let (mut coalgebra, _) = Coalgebra2::parse(&input, 10).unwrap();
+        coalgebra.coaction.maps.remove(&2);
+        assert_eq!(
+            coalgebra.validate(),
+            vec![Diagnostic::Dimensions {
+                grade: 2,
+                expected: (1, 3),
+                found: (0, 0)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_comodule_diagnostics() {
+        let (coalgebra, translator) =
+            Coalgebra2::parse(include_str!("../../../examples/direct/A(0).txt"), 10).unwrap();
+
+        // ξ1 acts twice on c, but ξ1 is primitive
+        let input = "- BASIS\na: 0\nb: 1\nc: 2\n\n- COACTION\n\
+            a: 1|a\nb: 1|b + xi1|a\nc: 1|c + xi1|b\n";
+        let comodule = kComodule::<i32, F2, RowMatrix<F2>>::parse(
+            input,
+            Arc::new(coalgebra.clone()),
+            &translator,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            comodule.validate(),
+            vec![Diagnostic::NotCoassociative {
+                element: "c".to_owned()
+            }]
+        );
+        assert!(kComodule::<i32, F2, RowMatrix<F2>>::parse_with_options(
+            input,
+            Arc::new(coalgebra),
+            &translator,
+            10,
+            VALIDATE
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_hopf_diagnostics() {
+        let input = include_str!("../../../examples/direct/A(1).txt");
+        Coalgebra2::parse_with_options(input, 10, VALIDATE).unwrap();
+        for input in [
+            include_str!("../../../examples/polynomial/A(2).txt"),
+            include_str!("../../../examples/polynomial/X(2).txt"),
+        ] {
+            let (coalgebra, _) = Coalgebra2::parse(input, 20).unwrap();
+            assert_eq!(coalgebra.validate_hopf(), vec![]);
+        }
+        for input in [
+            include_str!("../../../examples/polynomial/A(1)_p3.txt"),
+            include_str!("../../../examples/polynomial/A_p3.txt"),
+        ] {
+            let (coalgebra, _) = Coalgebra3::parse(input, 24).unwrap();
+            assert_eq!(coalgebra.validate_hopf(), vec![]);
+        }
+
+        // ξ1 ξ1^2 = ξ2 is of the right grade, but Δ(ξ2) is not Δ(ξ1)Δ(ξ1^2)
+        let broken = input.replace("xi1^1 | xi1^2 : xi1^3", "xi1^1 | xi1^2 : xi2^1");
+        let (coalgebra, _) = Coalgebra2::parse(&broken, 10).unwrap();
+        assert!(coalgebra
+            .validate_hopf()
+            .contains(&Diagnostic::NotMultiplicative {
+                left: "xi1^1".to_owned(),
+                right: "xi1^2".to_owned()
+            }));
+        assert!(Coalgebra2::parse_with_options(&broken, 10, VALIDATE).is_err());
+
+        let (coalgebra, _) =
+            Coalgebra2::parse(include_str!("../../../examples/direct/A(0).txt"), 10).unwrap();
+        assert_eq!(coalgebra.validate_hopf(), vec![Diagnostic::MissingProduct]);
+    }
+}
@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use crate::linalg::{graded::BasisElement, grading::Grading};
+use crate::linalg::{
+    graded::BasisElement,
+    grading::{Grading, LimitPolicy},
+};
 
 pub trait Comodule<G: Grading>: Sized {
     type Element: BasisElement;
@@ -21,7 +24,7 @@ pub trait Comodule<G: Grading>: Sized {
 
 pub trait ComoduleMorphism<G: Grading, M: Comodule<G>> {
     fn cokernel(&self) -> Self;
-    fn inject_codomain_to_cofree(&self, limit: G) -> Self; // Question: Shouldn't 'codomain' be 'cokernel'/'comodule'?
+    fn inject_codomain_to_cofree(&self, limit: G, policy: LimitPolicy<G>) -> Self; // Question: Shouldn't 'codomain' be 'cokernel'/'comodule'?
 
     fn zero_morphism(comodule: Arc<M>) -> Self;
 
@@ -34,7 +37,7 @@ pub trait ComoduleMorphism<G: Grading, M: Comodule<G>> {
     /// in a specific morphism we only need to know its gen_index
     /// in the resolution we add the s
     /// (from_dot, to_dot, value, line_type)
-    fn get_structure_lines(&self) -> Vec<(usize, usize, usize, String)>;
+    fn get_structure_lines(&self) -> Vec<(usize, usize, String, String)>;
 }
 
 pub trait Tensor<G: Grading> {
@@ -0,0 +1,457 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::linalg::{
+    field::Field,
+    graded::{BasisIndex, GradedLinearMap, GradedVectorSpace},
+    grading::Grading,
+    matrix::Matrix,
+};
+
+use super::{
+    kcoalgebra::kCoalgebra,
+    kcomodule::{kBasisElement, kComodule},
+    ktensor::kTensor,
+    traits::Tensor,
+};
+
+/// Reason why a coalgebra or comodule fails to satisfy the axioms
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diagnostic<G: Grading> {
+    /// The coaction in `grade` is `found` instead of `expected`, both as `(domain, codomain)`
+    Dimensions {
+        grade: G,
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// The coaction of `element` hits a tensor index which does not exist
+    UnknownTerm { element: String, index: usize },
+    /// The coaction of `element` in `grade` has a term of grade `left|right`
+    Inhomogeneous {
+        element: String,
+        grade: G,
+        left: G,
+        right: G,
+    },
+    /// Grade zero of the coalgebra is not spanned by a single unit
+    NotConnected,
+    /// `(ε ⊗ 1)Δ` does not give back `element`
+    LeftCounit { element: String },
+    /// `(1 ⊗ ε)Δ` does not give back `element`
+    RightCounit { element: String },
+    /// `(Δ ⊗ 1)Δ` and `(1 ⊗ Δ)Δ` differ on `element`
+    NotCoassociative { element: String },
+    /// The coalgebra has no product to check
+    MissingProduct,
+    /// The product in `grade` is `found` instead of `expected`, both as `(domain, codomain)`
+    ProductDimensions {
+        grade: G,
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// `Δ(left right)` differs from `Δ(left)Δ(right)`
+    NotMultiplicative { left: String, right: String },
+    /// The antipode could not be computed
+    NoAntipode { reason: String },
+    /// `Σ χ(x')x''` is not zero for `element`
+    Antipode { element: String },
+}
+
+impl<G: Grading> fmt::Display for Diagnostic<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::Dimensions {
+                grade,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Coaction in grade {} has size {}x{} instead of {}x{}",
+                grade, found.1, found.0, expected.1, expected.0
+            ),
+            Diagnostic::UnknownTerm { element, index } => write!(
+                f,
+                "Coaction of '{}' has a term with unknown tensor index {}",
+                element, index
+            ),
+            Diagnostic::Inhomogeneous {
+                element,
+                grade,
+                left,
+                right,
+            } => write!(
+                f,
+                "Coaction of '{}' is not homogenous: {} + {} != {}",
+                element, left, right, grade
+            ),
+            Diagnostic::NotConnected => write!(f, "Coalgebra has no unique unit in grade 0"),
+            Diagnostic::LeftCounit { element } => {
+                write!(f, "Left counit does not hold for '{}'", element)
+            }
+            Diagnostic::RightCounit { element } => {
+                write!(f, "Right counit does not hold for '{}'", element)
+            }
+            Diagnostic::NotCoassociative { element } => {
+                write!(f, "Coaction is not coassociative on '{}'", element)
+            }
+            Diagnostic::MissingProduct => write!(f, "Coalgebra has no product"),
+            Diagnostic::ProductDimensions {
+                grade,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Product in grade {} has size {}x{} instead of {}x{}",
+                grade, found.1, found.0, expected.1, expected.0
+            ),
+            Diagnostic::NotMultiplicative { left, right } => write!(
+                f,
+                "Coaction does not preserve the product of '{}' and '{}'",
+                left, right
+            ),
+            Diagnostic::NoAntipode { reason } => write!(f, "No antipode: {}", reason),
+            Diagnostic::Antipode { element } => {
+                write!(f, "Antipode is not two sided on '{}'", element)
+            }
+        }
+    }
+}
+
+/// Joins the diagnostics into a single error message
+pub fn diagnostics_to_string<G: Grading>(diagnostics: &[Diagnostic<G>]) -> String {
+    diagnostics
+        .iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Term `c (left ⊗ right)` of a coaction
+pub(crate) type Term<G, F> = (BasisIndex<G>, BasisIndex<G>, F);
+/// Element of a threefold tensor product, only the non zero coefficients are stored
+type ThreeTensor<G, F> = BTreeMap<(BasisIndex<G>, BasisIndex<G>, BasisIndex<G>), F>;
+
+/// Non zero terms of the coaction on `index`, which is assumed to fit the coaction
+pub(crate) fn terms<G: Grading, F: Field, M: Matrix<F>>(
+    coaction: &GradedLinearMap<G, F, M>,
+    tensor: &kTensor<G>,
+    index: BasisIndex<G>,
+) -> Vec<Term<G, F>> {
+    let map = &coaction.maps[&index.0];
+    (0..map.codomain())
+        .filter_map(|t_id| {
+            let val = map.get(index.1, t_id);
+            if val.is_zero() {
+                return None;
+            }
+            let (left, right) = tensor.deconstruct.get(&(index.0, t_id))?;
+            Some((*left, *right, val))
+        })
+        .collect()
+}
+
+fn add_term<K: Ord + Copy, F: Field>(sum: &mut BTreeMap<K, F>, key: K, val: F) {
+    let entry = sum.entry(key).or_insert(F::zero());
+    *entry += val;
+    if entry.is_zero() {
+        sum.remove(&key);
+    }
+}
+
+/// Checks the sizes of the coaction and that every term exists and lies in the right grade.
+///
+/// `left` is the space on the left of the tensor product, the coalgebra.
+/// Elements in grades with wrong sizes are skipped by the other checks.
+fn check_terms<G: Grading, F: Field, M: Matrix<F>>(
+    space: &GradedVectorSpace<G, kBasisElement>,
+    left: &GradedVectorSpace<G, kBasisElement>,
+    coaction: &GradedLinearMap<G, F, M>,
+    tensor: &kTensor<G>,
+    diagnostics: &mut Vec<Diagnostic<G>>,
+) -> Vec<BasisIndex<G>> {
+    let exists = |space: &GradedVectorSpace<G, kBasisElement>, (g, i): BasisIndex<G>| {
+        space.0.get(&g).is_some_and(|els| i < els.len())
+    };
+
+    let mut valid = vec![];
+    for (grade, elements) in &space.0 {
+        let expected = (elements.len(), tensor.get_dimension(grade));
+        let found = coaction
+            .maps
+            .get(grade)
+            .map_or((0, 0), |m| (m.domain(), m.codomain()));
+        if expected != found {
+            diagnostics.push(Diagnostic::Dimensions {
+                grade: *grade,
+                expected,
+                found,
+            });
+            continue;
+        }
+
+        let map = &coaction.maps[grade];
+        for (index, el) in elements.iter().enumerate() {
+            let mut fine = true;
+            for t_id in 0..map.codomain() {
+                if map.get(index, t_id).is_zero() {
+                    continue;
+                }
+                match tensor.deconstruct.get(&(*grade, t_id)) {
+                    Some(&(l, r)) if exists(left, l) && exists(space, r) => {
+                        if l.0 + r.0 != *grade {
+                            diagnostics.push(Diagnostic::Inhomogeneous {
+                                element: el.name.clone(),
+                                grade: *grade,
+                                left: l.0,
+                                right: r.0,
+                            });
+                            fine = false;
+                        }
+                    }
+                    _ => {
+                        diagnostics.push(Diagnostic::UnknownTerm {
+                            element: el.name.clone(),
+                            index: t_id,
+                        });
+                        fine = false;
+                    }
+                }
+            }
+            if fine {
+                valid.push((*grade, index));
+            }
+        }
+    }
+    valid
+}
+
+/// Whether the terms with the unit on the left are exactly `1 ⊗ index`
+fn left_counit<G: Grading, F: Field>(
+    terms: &[Term<G, F>],
+    unit: BasisIndex<G>,
+    index: BasisIndex<G>,
+) -> bool {
+    let found: Vec<_> = terms.iter().filter(|(l, _, _)| *l == unit).collect();
+    found == [&(unit, index, F::one())]
+}
+
+/// Whether the terms with the unit on the right are exactly `index ⊗ 1`
+fn right_counit<G: Grading, F: Field>(
+    terms: &[Term<G, F>],
+    unit: BasisIndex<G>,
+    index: BasisIndex<G>,
+) -> bool {
+    let found: Vec<_> = terms.iter().filter(|(_, r, _)| *r == unit).collect();
+    found == [&(index, unit, F::one())]
+}
+
+impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
+    /// Checks homogeneity, the counit axioms and coassociativity of the coaction
+    pub fn validate(&self) -> Vec<Diagnostic<G>> {
+        let mut diagnostics = vec![];
+        let valid = check_terms(
+            &self.space,
+            &self.space,
+            &self.coaction,
+            &self.tensor,
+            &mut diagnostics,
+        );
+
+        let unit = (G::zero(), 0);
+        let connected = self.space.0.get(&G::zero()).map(|els| els.len()) == Some(1);
+        if !connected {
+            diagnostics.push(Diagnostic::NotConnected);
+        }
+
+        // Terms of elements which failed earlier checks can not be trusted
+        if diagnostics.is_empty() {
+            for &index in &valid {
+                let element = self.space.0[&index.0][index.1].name.clone();
+                let delta = terms(&self.coaction, &self.tensor, index);
+
+                if !left_counit(&delta, unit, index) {
+                    diagnostics.push(Diagnostic::LeftCounit {
+                        element: element.clone(),
+                    });
+                }
+                if !right_counit(&delta, unit, index) {
+                    diagnostics.push(Diagnostic::RightCounit {
+                        element: element.clone(),
+                    });
+                }
+
+                let mut lhs = ThreeTensor::new();
+                let mut rhs = ThreeTensor::new();
+                for &(l, r, c) in &delta {
+                    for (ll, lr, c2) in terms(&self.coaction, &self.tensor, l) {
+                        add_term(&mut lhs, (ll, lr, r), c * c2);
+                    }
+                    for (rl, rr, c2) in terms(&self.coaction, &self.tensor, r) {
+                        add_term(&mut rhs, (l, rl, rr), c * c2);
+                    }
+                }
+                if lhs != rhs {
+                    diagnostics.push(Diagnostic::NotCoassociative { element });
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+impl<G: Grading, F: Field, M: Matrix<F>> kCoalgebra<G, F, M> {
+    /// Product of two basis elements as its non zero terms
+    fn product_terms(&self, left: BasisIndex<G>, right: BasisIndex<G>) -> Vec<(BasisIndex<G>, F)> {
+        match self.multiply(left, right) {
+            Some((grade, product)) => product
+                .into_iter()
+                .enumerate()
+                .filter(|(_, c)| !c.is_zero())
+                .map(|(i, c)| ((grade, i), c))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Checks that the coaction preserves the product, with the Koszul sign
+    /// `(a|b)(c|d) = (-1)^{|b||c|} ac|bd`, and that the antipode is two sided.
+    ///
+    /// The coalgebra itself is assumed to be valid.
+    pub fn validate_hopf(&self) -> Vec<Diagnostic<G>> {
+        let Some(product) = &self.product else {
+            return vec![Diagnostic::MissingProduct];
+        };
+        let mut diagnostics = vec![];
+        for (grade, elements) in &self.space.0 {
+            let expected = (self.tensor.get_dimension(grade), elements.len());
+            let found = product
+                .maps
+                .get(grade)
+                .map_or((0, 0), |m| (m.domain(), m.codomain()));
+            if expected != found {
+                diagnostics.push(Diagnostic::ProductDimensions {
+                    grade: *grade,
+                    expected,
+                    found,
+                });
+            }
+        }
+        if !diagnostics.is_empty() {
+            return diagnostics;
+        }
+
+        let name = |(g, i): BasisIndex<G>| self.space.0[&g][i].name.clone();
+        let coaction = |x| terms(&self.coaction, &self.tensor, x);
+
+        let mut pairs: Vec<_> = self.tensor.deconstruct.values().copied().collect();
+        pairs.sort();
+        for (x, y) in pairs {
+            let mut lhs = BTreeMap::new();
+            for (z, p) in self.product_terms(x, y) {
+                for (a, b, c) in coaction(z) {
+                    add_term(&mut lhs, (a, b), p * c);
+                }
+            }
+
+            let mut rhs = BTreeMap::new();
+            for (a, b, c1) in coaction(x) {
+                for (c, d, c2) in coaction(y) {
+                    let coeff = match b.0.is_odd() && c.0.is_odd() {
+                        true => -(c1 * c2),
+                        false => c1 * c2,
+                    };
+                    for (ac, p) in self.product_terms(a, c) {
+                        for (bd, q) in self.product_terms(b, d) {
+                            add_term(&mut rhs, (ac, bd), coeff * p * q);
+                        }
+                    }
+                }
+            }
+            if lhs != rhs {
+                diagnostics.push(Diagnostic::NotMultiplicative {
+                    left: name(x),
+                    right: name(y),
+                });
+            }
+        }
+
+        // The antipode is built with χ on the right, so check it on the left
+        let antipode = match self.antipode() {
+            Ok(antipode) => antipode,
+            Err(reason) => {
+                diagnostics.push(Diagnostic::NoAntipode { reason });
+                return diagnostics;
+            }
+        };
+        for (grade, elements) in &self.space.0 {
+            if *grade == G::zero() {
+                continue;
+            }
+            for index in 0..elements.len() {
+                let mut sum = BTreeMap::new();
+                for (a, b, c) in coaction((*grade, index)) {
+                    let chi = &antipode.maps[&a.0];
+                    for k in 0..chi.codomain() {
+                        let coeff = chi.get(a.1, k);
+                        if coeff.is_zero() {
+                            continue;
+                        }
+                        for (z, p) in self.product_terms((a.0, k), b) {
+                            add_term(&mut sum, z, c * coeff * p);
+                        }
+                    }
+                }
+                if !sum.is_empty() {
+                    diagnostics.push(Diagnostic::Antipode {
+                        element: name((*grade, index)),
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+impl<G: Grading, F: Field, M: Matrix<F>> kComodule<G, F, M> {
+    /// Checks homogeneity, the counit axiom and coassociativity of the coaction,
+    /// the coalgebra itself is assumed to be valid
+    pub fn validate(&self) -> Vec<Diagnostic<G>> {
+        let mut diagnostics = vec![];
+        let valid = check_terms(
+            &self.space,
+            &self.coalgebra.space,
+            &self.coaction,
+            &self.tensor,
+            &mut diagnostics,
+        );
+        if !diagnostics.is_empty() {
+            return diagnostics;
+        }
+
+        let coalgebra = &self.coalgebra;
+        for &index in &valid {
+            let element = self.space.0[&index.0][index.1].name.clone();
+            let rho = terms(&self.coaction, &self.tensor, index);
+
+            if !left_counit(&rho, (G::zero(), 0), index) {
+                diagnostics.push(Diagnostic::LeftCounit {
+                    element: element.clone(),
+                });
+            }
+
+            let mut lhs = ThreeTensor::new();
+            let mut rhs = ThreeTensor::new();
+            for &(a, m, c) in &rho {
+                for (al, ar, c2) in terms(&coalgebra.coaction, &coalgebra.tensor, a) {
+                    add_term(&mut lhs, (al, ar, m), c * c2);
+                }
+                for (ma, mm, c2) in terms(&self.coaction, &self.tensor, m) {
+                    add_term(&mut rhs, (a, ma, mm), c * c2);
+                }
+            }
+            if lhs != rhs {
+                diagnostics.push(Diagnostic::NotCoassociative { element });
+            }
+        }
+        diagnostics
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use crate::{
+    comodule::{
+        kcoalgebra::kCoalgebra, kcomodule::kComodule, parsers::parse_field, traits::Comodule,
+    },
+    export::SSeq,
+    linalg::{
+        extension_field::{F16, F25, F27, F4, F49, F8, F9},
+        f2_matrix::F2Matrix,
+        field::{Field, Fp32},
+        flat_matrix::FlatMatrix,
+        fp_matrix::FpMatrix,
+        grading::UniGrading,
+        matrix::Matrix,
+        rational::Q,
+    },
+    resolution::Resolution,
+};
+
+/// Values of the `- FIELD` section which `resolve` can handle
+pub const SUPPORTED_FIELDS: &[usize] = &[
+    0, 2, 3, 4, 5, 7, 8, 9, 11, 13, 16, 17, 19, 23, 25, 27, 29, 31, 49, 65521,
+];
+
+fn resolve_over<F: Field, M: Matrix<F>>(
+    coalgebra: &str,
+    comodule: Option<&str>,
+    s: usize,
+    max_grading: UniGrading,
+    name: &str,
+    verbose: bool,
+) -> Result<SSeq, String> {
+    let (coalgebra, translate) = kCoalgebra::<UniGrading, F, M>::parse(coalgebra, max_grading)?;
+    let coalgebra = Arc::new(coalgebra);
+
+    let comodule = match comodule {
+        Some(input) => kComodule::parse(input, coalgebra, &translate, max_grading)?,
+        None => kComodule::fp_comodule(coalgebra),
+    };
+
+    let mut res: Resolution<UniGrading, kComodule<UniGrading, F, M>> = Resolution::new(comodule);
+    if verbose {
+        res.resolve_to_s_with_print(s, max_grading);
+    } else {
+        res.resolve_to_s(s, max_grading);
+    }
+    Ok(res.generate_sseq(name))
+}
+
+/// Resolves `comodule`, or the trivial comodule if it is `None`, over `coalgebra` up to
+/// filtration `s`. The field is read from the `- FIELD` section of `coalgebra`:
+/// 0 is the rationals, primes use the packed matrix backends and prime powers use the
+/// Conway extension fields.
+pub fn resolve(
+    coalgebra: &str,
+    comodule: Option<&str>,
+    s: usize,
+    max_grading: UniGrading,
+    name: &str,
+    verbose: bool,
+) -> Result<SSeq, String> {
+    macro_rules! over {
+        ($f:ty, $m:ty) => {
+            resolve_over::<$f, $m>(coalgebra, comodule, s, max_grading, name, verbose)
+        };
+    }
+    macro_rules! over_prime {
+        ($p:literal) => {
+            over!(crate::linalg::field::Fp<$p>, FpMatrix<$p>)
+        };
+    }
+
+    match parse_field(coalgebra)? {
+        0 => over!(Q, FlatMatrix<Q>),
+        2 => over!(crate::linalg::field::F2, F2Matrix),
+        3 => over_prime!(3),
+        5 => over_prime!(5),
+        7 => over_prime!(7),
+        11 => over_prime!(11),
+        13 => over_prime!(13),
+        17 => over_prime!(17),
+        19 => over_prime!(19),
+        23 => over_prime!(23),
+        29 => over_prime!(29),
+        31 => over_prime!(31),
+        65521 => over!(Fp32<65521>, FlatMatrix<Fp32<65521>>),
+        4 => over!(F4, FlatMatrix<F4>),
+        8 => over!(F8, FlatMatrix<F8>),
+        16 => over!(F16, FlatMatrix<F16>),
+        9 => over!(F9, FlatMatrix<F9>),
+        27 => over!(F27, FlatMatrix<F27>),
+        25 => over!(F25, FlatMatrix<F25>),
+        49 => over!(F49, FlatMatrix<F49>),
+        field => Err(format!(
+            "Field {} is not supported, expected one of {:?}",
+            field, SUPPORTED_FIELDS
+        )),
+    }
+}
@@ -1,5 +1,6 @@
 // Library modules
 pub mod comodule;
+pub mod dispatch;
 pub mod export;
 pub mod linalg;
 pub mod polynomial;
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    echelon::EchelonForm, field::Field, graded::GradedLinearMap, grading::Grading, matrix::Matrix,
+};
+
+/// Complex `C_0 -> C_1 -> ...` of graded vector spaces of finite dimension.
+///
+/// `differentials[n]` goes from `C_n` to `C_{n + 1}` and preserves the grading,
+/// like the maps of a resolution.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradedChainComplex<G: Grading, F: Field, M: Matrix<F>> {
+    pub dimensions: Vec<BTreeMap<G, usize>>,
+    pub differentials: Vec<GradedLinearMap<G, F, M>>,
+}
+
+/// Homology in one degree, with a cycle in `C_n` representing every basis element
+pub type Homology<G, F> = BTreeMap<G, Vec<Vec<F>>>;
+
+impl<G: Grading, F: Field, M: Matrix<F>> GradedChainComplex<G, F, M> {
+    /// Reads the dimensions off the differentials, the last space is the codomain of the last one
+    pub fn new(differentials: Vec<GradedLinearMap<G, F, M>>) -> Self {
+        let mut dimensions: Vec<BTreeMap<G, usize>> = differentials
+            .iter()
+            .map(|d| d.maps.iter().map(|(g, m)| (*g, m.domain())).collect())
+            .collect();
+        if let Some(last) = differentials.last() {
+            dimensions.push(last.maps.iter().map(|(g, m)| (*g, m.codomain())).collect());
+        }
+        Self {
+            dimensions,
+            differentials,
+        }
+    }
+
+    /// Complex with a single space in degree zero
+    pub fn concentrated(dimensions: BTreeMap<G, usize>) -> Self {
+        Self {
+            dimensions: vec![dimensions],
+            differentials: vec![],
+        }
+    }
+
+    /// Number of degrees
+    pub fn len(&self) -> usize {
+        self.dimensions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dimensions.is_empty()
+    }
+
+    pub fn dimension(&self, n: usize, grade: &G) -> usize {
+        self.dimensions
+            .get(n)
+            .and_then(|d| d.get(grade).copied())
+            .unwrap_or(0)
+    }
+
+    /// The differential `C_n -> C_{n + 1}` in `grade`, zero where it is missing
+    pub fn differential(&self, n: usize, grade: &G) -> M {
+        self.differentials
+            .get(n)
+            .and_then(|d| d.maps.get(grade).cloned())
+            .unwrap_or_else(|| M::zero(self.dimension(n, grade), self.dimension(n + 1, grade)))
+    }
+
+    /// Checks that the differentials fit the dimensions and that `d d = 0`
+    pub fn verify(&self) -> Result<(), String> {
+        if self.differentials.len() != self.len().saturating_sub(1) {
+            return Err(format!(
+                "{} differentials do not fit {} spaces",
+                self.differentials.len(),
+                self.len()
+            ));
+        }
+        for (n, d) in self.differentials.iter().enumerate() {
+            for (g, m) in &d.maps {
+                if m.domain() != self.dimension(n, g) || m.codomain() != self.dimension(n + 1, g) {
+                    return Err(format!(
+                        "Differential {} in grade {} has size {}x{} instead of {}x{}",
+                        n,
+                        g,
+                        m.codomain(),
+                        m.domain(),
+                        self.dimension(n + 1, g),
+                        self.dimension(n, g)
+                    ));
+                }
+            }
+        }
+        for n in 1..self.differentials.len() {
+            for g in self.dimensions[n].keys() {
+                let square = self
+                    .differential(n, g)
+                    .compose(&self.differential(n - 1, g));
+                if (0..square.codomain()).any(|i| square.is_row_non_zero(i)) {
+                    return Err(format!(
+                        "d d is not zero from degree {} in grade {}",
+                        n - 1,
+                        g
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Cycles of `C_n` modulo the boundaries, grades without homology are left out
+    pub fn homology(&self, n: usize) -> Homology<G, F> {
+        let mut homology = BTreeMap::new();
+        for (g, &dimension) in self.dimensions.get(n).into_iter().flatten() {
+            let mut span = EchelonForm::new(dimension);
+            if n > 0 {
+                let boundaries = self.differential(n - 1, g).image();
+                for i in 0..boundaries.codomain() {
+                    span.push_row(&boundaries.get_row(i));
+                }
+            }
+
+            let cycles = match n + 1 < self.len() {
+                true => self.differential(n, g).kernel(),
+                false => M::identity(dimension),
+            };
+            let representatives: Vec<Vec<F>> = (0..cycles.codomain())
+                .map(|i| cycles.get_row(i))
+                .filter(|cycle| span.push_row(cycle))
+                .map(|cycle| cycle.to_vec())
+                .collect();
+            if !representatives.is_empty() {
+                homology.insert(*g, representatives);
+            }
+        }
+        homology
+    }
+
+    /// Dimension of the homology in every degree and grade
+    pub fn betti_numbers(&self) -> Vec<BTreeMap<G, usize>> {
+        (0..self.len())
+            .map(|n| {
+                self.homology(n)
+                    .into_iter()
+                    .map(|(g, reps)| (g, reps.len()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Moves `C_n` to degree `n + degrees`, with zero spaces below it
+    pub fn shift(&self, degrees: usize) -> Self {
+        let mut dimensions = vec![BTreeMap::new(); degrees];
+        dimensions.extend(self.dimensions.iter().cloned());
+        let mut differentials = vec![GradedLinearMap::empty(); degrees];
+        if self.is_empty() {
+            differentials.pop();
+        } else {
+            // The map from the last zero space into the old C_0
+            if let Some(d) = differentials.last_mut() {
+                d.maps = self.dimensions[0]
+                    .iter()
+                    .map(|(g, c)| (*g, M::zero(0, *c)))
+                    .collect();
+            }
+        }
+        differentials.extend(self.differentials.iter().cloned());
+        Self {
+            dimensions,
+            differentials,
+        }
+    }
+
+    /// Adds `grade` to every grade
+    pub fn shift_grading(&self, grade: G) -> Self {
+        Self {
+            dimensions: self
+                .dimensions
+                .iter()
+                .map(|d| d.iter().map(|(g, c)| (*g + grade, *c)).collect())
+                .collect(),
+            differentials: self
+                .differentials
+                .iter()
+                .map(|d| {
+                    GradedLinearMap::from(
+                        d.maps
+                            .iter()
+                            .map(|(g, m)| (*g + grade, m.clone()))
+                            .collect::<BTreeMap<G, M>>(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Tensor product with differential `d(x y) = d(x) y + (-1)^i x d(y)` for `x` in `C_i`.
+    ///
+    /// In every degree and grade the basis runs over the blocks `C_i ⊗ D_j` by increasing `i`
+    /// and grade of `C_i`, within a block `x_a ⊗ y_b` has index `a * dim(D_j) + b`.
+    pub fn tensor(&self, other: &Self) -> Self {
+        if self.is_empty() || other.is_empty() {
+            return Self {
+                dimensions: vec![],
+                differentials: vec![],
+            };
+        }
+        let len = self.len() + other.len() - 1;
+
+        // Offset of the block C_i(g) ⊗ D_{n - i}(h) in degree n and grade g + h
+        let mut offsets: Vec<BTreeMap<(usize, G, G), usize>> = vec![BTreeMap::new(); len];
+        let mut dimensions: Vec<BTreeMap<G, usize>> = vec![BTreeMap::new(); len];
+        for n in 0..len {
+            for i in n.saturating_sub(other.len() - 1)..self.len().min(n + 1) {
+                for (g, c) in &self.dimensions[i] {
+                    for (h, d) in &other.dimensions[n - i] {
+                        let total = dimensions[n].entry(*g + *h).or_insert(0);
+                        offsets[n].insert((i, *g, *h), *total);
+                        *total += c * d;
+                    }
+                }
+            }
+        }
+
+        let differentials = (0..len - 1)
+            .map(|n| {
+                let mut maps: BTreeMap<G, M> = dimensions[n]
+                    .iter()
+                    .map(|(g, c)| {
+                        (
+                            *g,
+                            M::zero(*c, dimensions[n + 1].get(g).copied().unwrap_or(0)),
+                        )
+                    })
+                    .collect();
+                for (&(i, g, h), &offset) in &offsets[n] {
+                    let j = n - i;
+                    let (c, d) = (self.dimension(i, &g), other.dimension(j, &h));
+                    // Blocks of the next degree only exist where the spaces are there
+                    let map = maps.get_mut(&(g + h)).unwrap();
+                    if let Some(&target) = offsets[n + 1].get(&(i + 1, g, h)) {
+                        let dx = self.differential(i, &g);
+                        for a in 0..c {
+                            for a2 in 0..dx.codomain() {
+                                let val = dx.get(a, a2);
+                                if !val.is_zero() {
+                                    for b in 0..d {
+                                        map.add_at(offset + a * d + b, target + a2 * d + b, val);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if let Some(&target) = offsets[n + 1].get(&(i, g, h)) {
+                        let dy = other.differential(j, &h);
+                        let d2 = dy.codomain();
+                        for b in 0..d {
+                            for b2 in 0..d2 {
+                                let val = match i % 2 {
+                                    0 => dy.get(b, b2),
+                                    _ => -dy.get(b, b2),
+                                };
+                                if !val.is_zero() {
+                                    for a in 0..c {
+                                        map.add_at(offset + a * d + b, target + a * d2 + b2, val);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                GradedLinearMap::from(maps)
+            })
+            .collect();
+
+        Self {
+            dimensions,
+            differentials,
+        }
+    }
+}
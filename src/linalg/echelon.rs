@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use super::field::Field;
+
+/// Reduced row echelon basis of a growing set of rows.
+///
+/// Rows are reduced when they are pushed, so appending a row costs one pass over the basis
+/// instead of a full rref. The rows are kept in insertion order, every row is normalized
+/// on its pivot and all other rows are zero in its pivot column.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EchelonForm<F: Field> {
+    rows: Vec<Vec<F>>,
+    pivots: Vec<usize>,
+    /// For every column the index of the row which has it as pivot
+    pivot_row: Vec<Option<usize>>,
+}
+
+impl<F: Field> EchelonForm<F> {
+    pub fn new(width: usize) -> Self {
+        Self {
+            rows: vec![],
+            pivots: vec![],
+            pivot_row: vec![None; width],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.pivot_row.len()
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows(&self) -> &[Vec<F>] {
+        &self.rows
+    }
+
+    /// Pivot column of every row, in the same order as `rows`
+    pub fn pivots(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    pub fn is_pivot(&self, col: usize) -> bool {
+        self.pivot_row[col].is_some()
+    }
+
+    /// Subtracts the basis from v, the result is zero precisely when v is in the span
+    pub fn reduce(&self, v: &[F]) -> Vec<F> {
+        debug_assert_eq!(v.len(), self.width(), "Row does not have the right width");
+        let mut v = v.to_vec();
+        for (row, &pivot) in self.rows.iter().zip(&self.pivots) {
+            let factor = v[pivot];
+            if !factor.is_zero() {
+                for (x, y) in v.iter_mut().zip(row) {
+                    *x -= factor * *y;
+                }
+            }
+        }
+        v
+    }
+
+    /// Adds a row to the span, returns false if it already was in the span
+    pub fn push_row(&mut self, row: &[F]) -> bool {
+        let mut v = self.reduce(row);
+        let pivot = match v.iter().position(|x| !x.is_zero()) {
+            Some(p) => p,
+            None => return false,
+        };
+
+        let inv = v[pivot].inv().expect("Pivot should be invertible");
+        v.iter_mut().for_each(|x| *x *= inv);
+
+        // Clear the new pivot column in the other rows to stay reduced
+        for other in self.rows.iter_mut() {
+            let factor = other[pivot];
+            if !factor.is_zero() {
+                for (x, y) in other.iter_mut().zip(&v) {
+                    *x -= factor * *y;
+                }
+            }
+        }
+
+        self.pivot_row[pivot] = Some(self.rows.len());
+        self.pivots.push(pivot);
+        self.rows.push(v);
+        true
+    }
+
+    /// Smallest column in which a vector of the kernel is non-zero, where the rows are seen
+    /// as the rows of a matrix. This is the column of `kernel().first_non_zero_entry()`.
+    pub fn kernel_lead(&self) -> Option<usize> {
+        // The kernel vector of a free column f is e_f - sum_p row_p[f] e_p.
+        // Reduced rows are only non-zero in free columns outside of their pivot.
+        let first_free = self.pivot_row.iter().position(|p| p.is_none())?;
+        let lead = self
+            .rows
+            .iter()
+            .zip(&self.pivots)
+            .filter(|(row, &p)| p < first_free && row.iter().filter(|x| !x.is_zero()).count() > 1)
+            .map(|(_, &p)| p)
+            .min();
+        Some(lead.map_or(first_free, |p| p.min(first_free)))
+    }
+}
@@ -0,0 +1,319 @@
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use super::field::{CRing, Field};
+
+/// Largest extension degree `n` supported by `Fq`
+pub const MAX_DEGREE: usize = 8;
+
+/// Describes the finite field F_{p^n} = F_p[x] / (f) for a monic irreducible `f` of degree `n`.
+///
+/// Implement this through the `galois_extension!` macro.
+pub trait GaloisExtension: 'static + Clone + Copy + Debug + PartialEq + Eq + Sync + Send {
+    const P: u8;
+    /// Lower coefficients of the modulus, f = x^n + MODULUS[n-1] x^{n-1} + ... + MODULUS[0]
+    const MODULUS: &'static [u8];
+    /// Name of the class of x, used when parsing and printing elements
+    const GENERATOR: &'static str;
+
+    fn degree() -> usize {
+        Self::MODULUS.len()
+    }
+}
+
+/// Defines a `GaloisExtension` from a prime, the lower coefficients of a monic irreducible
+/// polynomial and the name of its root, e.g.
+/// `galois_extension!(F4Ext, 2, [1, 1], "w")` gives F_4 with `w^2 = w + 1`.
+#[macro_export]
+macro_rules! galois_extension {
+    ($name:ident, $p:expr, [$($c:expr),+ $(,)?], $gen:expr) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct $name;
+
+        impl $crate::linalg::extension_field::GaloisExtension for $name {
+            const P: u8 = $p;
+            const MODULUS: &'static [u8] = &[$($c),+];
+            const GENERATOR: &'static str = $gen;
+        }
+
+        const _: () = assert!(
+            <$name as $crate::linalg::extension_field::GaloisExtension>::MODULUS.len()
+                <= $crate::linalg::extension_field::MAX_DEGREE,
+            "Extension degree is too large"
+        );
+    };
+}
+
+// Conway polynomials
+galois_extension!(F4Ext, 2, [1, 1], "w");
+galois_extension!(F8Ext, 2, [1, 1, 0], "w");
+galois_extension!(F16Ext, 2, [1, 1, 0, 0], "w");
+galois_extension!(F9Ext, 3, [2, 2], "w");
+galois_extension!(F27Ext, 3, [1, 2, 0], "w");
+galois_extension!(F25Ext, 5, [2, 4], "w");
+galois_extension!(F49Ext, 7, [3, 6], "w");
+
+pub type F4 = Fq<F4Ext>;
+pub type F8 = Fq<F8Ext>;
+pub type F16 = Fq<F16Ext>;
+pub type F9 = Fq<F9Ext>;
+pub type F27 = Fq<F27Ext>;
+pub type F25 = Fq<F25Ext>;
+pub type F49 = Fq<F49Ext>;
+
+/// Element of F_{p^n}, stored as its coefficients in the basis 1, x, ..., x^{n-1}.
+///
+/// Coefficients beyond `n` are always zero.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Fq<E: GaloisExtension> {
+    pub coeffs: [u8; MAX_DEGREE],
+    __: PhantomData<E>,
+}
+
+impl<E: GaloisExtension> Fq<E> {
+    /// Takes the coefficients of 1, x, x^2, ... and reduces them modulo p and the modulus
+    pub fn from_coefficients(coeffs: &[u8]) -> Self {
+        let mut wide = vec![0u32; coeffs.len().max(E::degree())];
+        for (w, c) in wide.iter_mut().zip(coeffs) {
+            *w = *c as u32 % E::P as u32;
+        }
+        Self::reduce(&mut wide)
+    }
+
+    /// The class of x
+    pub fn generator() -> Self {
+        Self::from_coefficients(&[0, 1])
+    }
+
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut result = Self::one();
+        let mut base = self;
+        while exp > 0 {
+            if exp % 2 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exp /= 2;
+        }
+        result
+    }
+
+    fn from_integer(n: i64) -> Self {
+        let mut coeffs = [0; MAX_DEGREE];
+        coeffs[0] = n.rem_euclid(E::P as i64) as u8;
+        Self {
+            coeffs,
+            __: PhantomData,
+        }
+    }
+
+    /// Reduces a polynomial with coefficients below p modulo the defining polynomial
+    fn reduce(wide: &mut [u32]) -> Self {
+        let p = E::P as u32;
+        let n = E::degree();
+        for d in (n..wide.len()).rev() {
+            let c = wide[d];
+            if c == 0 {
+                continue;
+            }
+            // x^d = x^{d-n} * x^n = - x^{d-n} * (MODULUS[0] + ... + MODULUS[n-1] x^{n-1})
+            for (i, m) in E::MODULUS.iter().enumerate() {
+                let k = d - n + i;
+                wide[k] = (wide[k] + c * (p - *m as u32)) % p;
+            }
+        }
+        let mut coeffs = [0; MAX_DEGREE];
+        for (c, w) in coeffs.iter_mut().zip(wide.iter()).take(n) {
+            *c = *w as u8;
+        }
+        Self {
+            coeffs,
+            __: PhantomData,
+        }
+    }
+
+    /// Parses a single term such as `3`, `w`, `2w^3` or `2*w^3`
+    fn parse_term(term: &str) -> Result<Self, String> {
+        let err = || format!("Field: {} could not be parsed", term);
+        match term.split_once(E::GENERATOR) {
+            None => Ok(Self::from_integer(term.parse().map_err(|_| err())?)),
+            Some((scalar, exponent)) => {
+                let scalar = scalar.strip_suffix('*').unwrap_or(scalar);
+                let scalar = match scalar {
+                    "" => 1,
+                    s => s.parse().map_err(|_| err())?,
+                };
+                let exponent = match exponent {
+                    "" => 1,
+                    e => e
+                        .strip_prefix('^')
+                        .ok_or_else(err)?
+                        .parse()
+                        .map_err(|_| err())?,
+                };
+                Ok(Self::from_integer(scalar) * Self::generator().pow(exponent))
+            }
+        }
+    }
+}
+
+impl<E: GaloisExtension> Add for Fq<E> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<E: GaloisExtension> Sub for Fq<E> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<E: GaloisExtension> Neg for Fq<E> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+impl<E: GaloisExtension> Mul for Fq<E> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let n = E::degree();
+        let p = E::P as u32;
+        // A product of two polynomials of degree below n has degree at most 2n - 2
+        let mut wide = [0u32; 2 * MAX_DEGREE - 1];
+        for i in 0..n {
+            if self.coeffs[i] == 0 {
+                continue;
+            }
+            for j in 0..n {
+                wide[i + j] = (wide[i + j] + self.coeffs[i] as u32 * rhs.coeffs[j] as u32) % p;
+            }
+        }
+        Self::reduce(&mut wide[..2 * n - 1])
+    }
+}
+
+impl<E: GaloisExtension> AddAssign for Fq<E> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (c, r) in self.coeffs.iter_mut().zip(rhs.coeffs) {
+            *c = ((*c as u16 + r as u16) % E::P as u16) as u8;
+        }
+    }
+}
+
+impl<E: GaloisExtension> SubAssign for Fq<E> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (c, r) in self.coeffs.iter_mut().zip(rhs.coeffs) {
+            *c = ((E::P as u16 + *c as u16 - r as u16) % E::P as u16) as u8;
+        }
+    }
+}
+
+impl<E: GaloisExtension> MulAssign for Fq<E> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<E: GaloisExtension> std::iter::Sum for Fq<E> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<E: GaloisExtension> std::fmt::Debug for Fq<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let terms: Vec<String> = (0..E::degree())
+            .rev()
+            .filter(|&i| self.coeffs[i] != 0)
+            .map(|i| match (self.coeffs[i], i) {
+                (c, 0) => format!("{}", c),
+                (1, 1) => E::GENERATOR.to_string(),
+                (c, 1) => format!("{}{}", c, E::GENERATOR),
+                (1, i) => format!("{}^{}", E::GENERATOR, i),
+                (c, i) => format!("{}{}^{}", c, E::GENERATOR, i),
+            })
+            .collect();
+        if terms.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", terms.join("+"))
+        }
+    }
+}
+
+impl<E: GaloisExtension> CRing for Fq<E> {
+    fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|c| *c == 0)
+    }
+
+    fn one() -> Self {
+        Self::from_integer(1)
+    }
+
+    fn zero() -> Self {
+        Self::from_integer(0)
+    }
+
+    /// Parses sums of terms like `w^2+2w-1` where `w` is the generator of the extension
+    fn parse(input: &str) -> Result<Self, String> {
+        let input: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+        if input.is_empty() {
+            return Err("Field: empty input could not be parsed".to_owned());
+        }
+
+        let mut sum = Self::zero();
+        let mut start = 0;
+        for (i, c) in input.char_indices().chain([(input.len(), '+')]) {
+            if (c == '+' || c == '-') && i > start {
+                let term = &input[start..i];
+                sum += match term.strip_prefix('-') {
+                    Some(t) => -Self::parse_term(t)?,
+                    None => Self::parse_term(term.strip_prefix('+').unwrap_or(term))?,
+                };
+                start = i;
+            }
+        }
+        Ok(sum)
+    }
+}
+
+impl<E: GaloisExtension> Field for Fq<E> {
+    fn inv(self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        // The multiplicative group has order p^n - 1.
+        // If the modulus is reducible this fails for zero divisors, which we check for.
+        let inverse = self.pow(Self::get_order() as u64 - 2);
+        (inverse * self == Self::one()).then_some(inverse)
+    }
+
+    fn get_characteristic() -> usize {
+        E::P as usize
+    }
+
+    fn get_order() -> usize {
+        (E::P as usize).pow(E::degree() as u32)
+    }
+
+    fn as_usize(self) -> usize {
+        self.coeffs[..E::degree()]
+            .iter()
+            .rev()
+            .fold(0, |acc, c| acc * E::P as usize + *c as usize)
+    }
+}
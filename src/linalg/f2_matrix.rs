@@ -0,0 +1,390 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    field::{CRing, F2},
+    flat_matrix::FlatMatrix,
+    matrix::{Matrix, RModMorphism, PARALLEL_COMPOSE_THRESHOLD},
+};
+
+const LIMB_BITS: usize = u64::BITS as usize;
+
+/// Number of rhs rows combined into one lookup table by `compose_four_russians`
+const RUSSIAN_BITS: usize = 8;
+
+/// Below this many rows building the lookup tables costs more than it saves
+const FOUR_RUSSIANS_MIN_ROWS: usize = 64;
+
+/// Dense F2 matrix where every row is packed into `u64` limbs.
+///
+/// Bit `j % 64` of limb `j / 64` of a row is the entry in domain index `j`.
+/// Bits beyond `domain` are always zero, so rows can be compared and XORed limb-wise.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct F2Matrix {
+    pub data: Vec<u64>,
+    pub domain: usize,
+    pub codomain: usize,
+    pub limbs: usize,
+}
+
+impl F2Matrix {
+    fn limbs_for(domain: usize) -> usize {
+        domain.div_ceil(LIMB_BITS)
+    }
+
+    fn row(&self, row: usize) -> &[u64] {
+        &self.data[row * self.limbs..(row + 1) * self.limbs]
+    }
+
+    fn row_mut(&mut self, row: usize) -> &mut [u64] {
+        &mut self.data[row * self.limbs..(row + 1) * self.limbs]
+    }
+
+    fn bit(&self, row: usize, col: usize) -> bool {
+        (self.data[row * self.limbs + col / LIMB_BITS] >> (col % LIMB_BITS)) & 1 == 1
+    }
+
+    fn flip_bit(&mut self, row: usize, col: usize) {
+        self.data[row * self.limbs + col / LIMB_BITS] ^= 1 << (col % LIMB_BITS);
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        for l in 0..self.limbs {
+            self.data.swap(a * self.limbs + l, b * self.limbs + l);
+        }
+    }
+
+    /// target += source, only touching the limbs from `from_limb` onwards
+    fn xor_row_into(&mut self, source: usize, target: usize, from_limb: usize) {
+        let limbs = self.limbs;
+        let (src, tgt) = if source < target {
+            let (l, r) = self.data.split_at_mut(target * limbs);
+            (&l[source * limbs..(source + 1) * limbs], &mut r[..limbs])
+        } else {
+            let (l, r) = self.data.split_at_mut(source * limbs);
+            (&r[..limbs], &mut l[target * limbs..(target + 1) * limbs])
+        };
+        for l in from_limb..limbs {
+            tgt[l] ^= src[l];
+        }
+    }
+
+    /// Index of the first set bit in `row` at or after `from`
+    fn first_set_from(&self, row: usize, from: usize) -> Option<usize> {
+        if from >= self.domain {
+            return None;
+        }
+        let row = self.row(row);
+        let mut limb = from / LIMB_BITS;
+        let mut word = row[limb] & (u64::MAX << (from % LIMB_BITS));
+        loop {
+            if word != 0 {
+                return Some(limb * LIMB_BITS + word.trailing_zeros() as usize);
+            }
+            limb += 1;
+            if limb == self.limbs {
+                return None;
+            }
+            word = row[limb];
+        }
+    }
+
+    /// Iterates over the domain indices of the non-zero entries of `row`
+    fn set_bits(&self, row: usize) -> impl Iterator<Item = usize> + '_ {
+        self.row(row).iter().enumerate().flat_map(|(l, &limb)| {
+            let mut word = limb;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(l * LIMB_BITS + bit)
+            })
+        })
+    }
+
+    /// "Method of four Russians" multiply. For every group of `RUSSIAN_BITS` rows of rhs
+    /// all their sums are tabulated once, then every output row adds one table entry
+    /// per group instead of one rhs row per set bit.
+    fn compose_four_russians(&self, rhs: &Self) -> Self {
+        let mut compose = Self::zero(rhs.domain, self.codomain);
+        let limbs = compose.limbs;
+        if limbs == 0 {
+            return compose;
+        }
+
+        // One pass over the rows per group, so only spawn tasks when the product is large
+        let parallel = self.codomain * self.domain * rhs.domain >= PARALLEL_COMPOSE_THRESHOLD;
+        let mut table = vec![0u64; (1 << RUSSIAN_BITS) * limbs];
+        for k_start in (0..self.domain).step_by(RUSSIAN_BITS) {
+            let bits = RUSSIAN_BITS.min(self.domain - k_start);
+
+            // Entry i is the sum of the rows k_start + b for all set bits b of i,
+            // built from the entry without its lowest bit
+            for i in 1..(1usize << bits) {
+                let without_lowest = i & (i - 1);
+                let (done, todo) = table.split_at_mut(i * limbs);
+                let prev = &done[without_lowest * limbs..(without_lowest + 1) * limbs];
+                let row = rhs.row(k_start + i.trailing_zeros() as usize);
+                for ((t, p), r) in todo[..limbs].iter_mut().zip(prev).zip(row) {
+                    *t = p ^ r;
+                }
+            }
+
+            // RUSSIAN_BITS divides LIMB_BITS, so a group never straddles two limbs
+            let (limb, shift) = (k_start / LIMB_BITS, k_start % LIMB_BITS);
+            let mask = (1u64 << bits) - 1;
+            let add_entry = |(x, out): (usize, &mut [u64])| {
+                let i = ((self.data[x * self.limbs + limb] >> shift) & mask) as usize;
+                if i != 0 {
+                    for (o, t) in out.iter_mut().zip(&table[i * limbs..(i + 1) * limbs]) {
+                        *o ^= t;
+                    }
+                }
+            };
+            if parallel {
+                compose
+                    .data
+                    .par_chunks_mut(limbs)
+                    .enumerate()
+                    .for_each(add_entry);
+            } else {
+                compose
+                    .data
+                    .chunks_mut(limbs)
+                    .enumerate()
+                    .for_each(add_entry);
+            }
+        }
+        compose
+    }
+
+    fn rref_kernel(&self) -> Self {
+        let pivots = self.pivots();
+        let mut is_pivot = vec![false; self.domain];
+        for &(col, _) in &pivots {
+            is_pivot[col] = true;
+        }
+        let free_vars: Vec<usize> = (0..self.domain).filter(|&j| !is_pivot[j]).collect();
+
+        let mut kernel = Self::zero(self.domain, free_vars.len());
+        for (i, &free_var) in free_vars.iter().enumerate() {
+            kernel.flip_bit(i, free_var);
+            for &(pivot_col, row) in &pivots {
+                if self.bit(row, free_var) {
+                    kernel.flip_bit(i, pivot_col);
+                }
+            }
+        }
+        kernel
+    }
+}
+
+impl Matrix<F2> for F2Matrix {
+    fn kernel(&self) -> Self {
+        let mut clone = self.clone();
+        clone.rref();
+        let mut kernel = clone.rref_kernel();
+        kernel.rref();
+        kernel
+    }
+
+    fn rref(&mut self) {
+        let mut lead = 0;
+
+        for r in 0..self.codomain {
+            if lead >= self.domain {
+                break;
+            }
+
+            let pivot_row = loop {
+                match (r..self.codomain).find(|&i| self.bit(i, lead)) {
+                    Some(i) => break i,
+                    None => {
+                        lead += 1;
+                        if lead == self.domain {
+                            return;
+                        }
+                    }
+                }
+            };
+
+            self.swap_rows(pivot_row, r);
+
+            // Entries left of the lead column are zero in the pivot row
+            let from_limb = lead / LIMB_BITS;
+            for i in 0..self.codomain {
+                if i != r && self.bit(i, lead) {
+                    self.xor_row_into(r, i, from_limb);
+                }
+            }
+
+            lead += 1;
+        }
+    }
+
+    fn pivots(&self) -> Vec<(usize, usize)> {
+        let mut col = 0;
+        let mut pivots = vec![];
+        for row in 0..self.codomain {
+            match self.first_set_from(row, col) {
+                Some(c) => {
+                    pivots.push((c, row));
+                    col = c + 1;
+                }
+                None => {
+                    col = self.domain;
+                }
+            }
+        }
+        pivots
+    }
+
+    fn first_non_zero_entry(&self) -> Option<(usize, usize)> {
+        (0..self.codomain).find_map(|codom_id| {
+            self.first_set_from(codom_id, 0)
+                .map(|dom_id| (codom_id, dom_id))
+        })
+    }
+}
+
+impl RModMorphism<F2> for F2Matrix {
+    fn zero(domain: usize, codomain: usize) -> Self {
+        let limbs = Self::limbs_for(domain);
+        Self {
+            data: vec![0; limbs * codomain],
+            domain,
+            codomain,
+            limbs,
+        }
+    }
+
+    fn identity(d: usize) -> Self {
+        let mut id = Self::zero(d, d);
+        for i in 0..d {
+            id.flip_bit(i, i);
+        }
+        id
+    }
+
+    fn get(&self, domain: usize, codomain: usize) -> F2 {
+        F2(self.bit(codomain, domain) as u8)
+    }
+
+    fn set(&mut self, domain: usize, codomain: usize, f: F2) {
+        if self.bit(codomain, domain) == f.is_zero() {
+            self.flip_bit(codomain, domain);
+        }
+    }
+
+    fn add_at(&mut self, domain: usize, codomain: usize, f: F2) {
+        if !f.is_zero() {
+            self.flip_bit(codomain, domain);
+        }
+    }
+
+    type Row<'a> = Vec<F2>;
+
+    fn get_row(&self, codomain: usize) -> Vec<F2> {
+        (0..self.domain)
+            .map(|domain| self.get(domain, codomain))
+            .collect()
+    }
+
+    fn set_row(&mut self, codomain: usize, row: &[F2]) {
+        debug_assert_eq!(row.len(), self.domain, "Row length does not match domain");
+        let target = self.row_mut(codomain);
+        target.fill(0);
+        for (j, f) in row.iter().enumerate() {
+            if !f.is_zero() {
+                target[j / LIMB_BITS] |= 1 << (j % LIMB_BITS);
+            }
+        }
+    }
+
+    fn is_row_non_zero(&self, codomain: usize) -> bool {
+        self.row(codomain).iter().any(|&l| l != 0)
+    }
+
+    // domain l == codomain r, l \circ r
+    fn compose(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(
+            self.domain, rhs.codomain,
+            "Matrix domain not equal to codomain"
+        );
+
+        if self.codomain >= FOUR_RUSSIANS_MIN_ROWS && self.domain >= RUSSIAN_BITS {
+            return self.compose_four_russians(rhs);
+        }
+
+        let mut compose = Self::zero(rhs.domain, self.codomain);
+        for x in 0..self.codomain {
+            for k in self.set_bits(x) {
+                let start = x * compose.limbs;
+                for (l, limb) in rhs.row(k).iter().enumerate() {
+                    compose.data[start + l] ^= limb;
+                }
+            }
+        }
+        compose
+    }
+
+    fn transpose(&self) -> Self {
+        let mut trans = Self::zero(self.codomain, self.domain);
+        for i in 0..self.codomain {
+            for j in self.set_bits(i) {
+                trans.flip_bit(j, i);
+            }
+        }
+        trans
+    }
+
+    fn domain(&self) -> usize {
+        self.domain
+    }
+
+    fn codomain(&self) -> usize {
+        self.codomain
+    }
+
+    fn vstack(&mut self, other: &mut Self) {
+        debug_assert_eq!(
+            self.domain(),
+            other.domain(),
+            "Domains of the two matrices do not have the same dimension"
+        );
+
+        self.data.append(&mut other.data);
+        self.codomain += other.codomain;
+    }
+
+    fn block_sum(&mut self, other: &Self) {
+        let mut new = Self::zero(self.domain + other.domain, self.codomain + other.codomain);
+
+        for i in 0..self.codomain {
+            new.row_mut(i)[..self.limbs].copy_from_slice(self.row(i));
+        }
+
+        for i in 0..other.codomain {
+            for j in other.set_bits(i) {
+                new.flip_bit(self.codomain + i, self.domain + j);
+            }
+        }
+
+        *self = new
+    }
+}
+
+impl From<&FlatMatrix<F2>> for F2Matrix {
+    fn from(value: &FlatMatrix<F2>) -> Self {
+        let mut m = Self::zero(value.domain, value.codomain);
+        for i in 0..value.codomain {
+            m.set_row(i, value.get_row(i));
+        }
+        m
+    }
+}
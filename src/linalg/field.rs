@@ -5,7 +5,6 @@ use std::{
 
 pub trait CRing:
     Clone
-    + Copy
     + Debug
     + Sized
     + PartialEq
@@ -27,15 +26,27 @@ pub trait CRing:
     fn parse(input: &str) -> Result<Self, String>;
 
     fn dot_product(l: &Vec<Self>, r: &Vec<Self>) -> Self {
-        l.iter().zip(r.iter()).map(|(x, y)| *x * *y).sum()
+        l.iter()
+            .zip(r.iter())
+            .map(|(x, y)| x.clone() * y.clone())
+            .sum()
     }
 }
 
-pub trait Field: CRing {
+pub trait Field: CRing + Copy {
     fn inv(self) -> Option<Self>;
     fn get_characteristic() -> usize;
+    /// Number of elements, 0 for infinite fields
+    fn get_order() -> usize {
+        Self::get_characteristic()
+    }
 
     fn as_usize(self) -> usize;
+
+    /// Coefficient of a structure line in exported charts, written the way `parse` reads it
+    fn export_value(self) -> String {
+        format!("{:?}", self)
+    }
 }
 
 impl CRing for f64 {
@@ -312,3 +323,149 @@ impl Field for F2 {
         self.0 as usize
     }
 }
+
+/// Prime field for primes that do not fit in a `u8`, e.g. `Fp32<65521>`.
+///
+/// Products are reduced with Barrett reduction, which replaces the division by `P`
+/// with a multiplication by the precomputed `floor(2^64 / P)`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Fp32<const P: u32>(pub u32);
+
+impl<const P: u32> Fp32<P> {
+    const MU: u128 = (1u128 << 64) / P as u128;
+
+    fn reduce(x: u64) -> u32 {
+        let q = ((x as u128 * Self::MU) >> 64) as u64;
+        let mut r = x - q * P as u64;
+        // The quotient estimate is at most two below the real quotient
+        while r >= P as u64 {
+            r -= P as u64;
+        }
+        r as u32
+    }
+}
+
+impl<const P: u32> Add for Fp32<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let sum = self.0 as u64 + rhs.0 as u64;
+        if sum >= P as u64 {
+            Fp32((sum - P as u64) as u32)
+        } else {
+            Fp32(sum as u32)
+        }
+    }
+}
+
+impl<const P: u32> Mul for Fp32<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Fp32(Self::reduce(self.0 as u64 * rhs.0 as u64))
+    }
+}
+
+impl<const P: u32> Sub for Fp32<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl<const P: u32> Neg for Fp32<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.0 == 0 {
+            self
+        } else {
+            Fp32(P - self.0)
+        }
+    }
+}
+
+impl<const P: u32> AddAssign for Fp32<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u32> MulAssign for Fp32<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: u32> SubAssign for Fp32<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u32> std::fmt::Debug for Fp32<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u32> std::iter::Sum for Fp32<P> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        // Summands are below 2^32, so only reduce when the sum gets close to overflowing
+        let mut sum: u64 = 0;
+        for a in iter {
+            sum += a.0 as u64;
+            if sum >= 1 << 63 {
+                sum %= P as u64;
+            }
+        }
+        Fp32((sum % P as u64) as u32)
+    }
+}
+
+impl<const P: u32> CRing for Fp32<P> {
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn one() -> Self {
+        Fp32(1)
+    }
+
+    fn zero() -> Self {
+        Fp32(0)
+    }
+
+    fn parse(input: &str) -> Result<Self, String> {
+        let chr: i64 = input
+            .parse()
+            .map_err(|_| format!("Field: {} could not be parsed", input))?;
+        Ok(Self(chr.rem_euclid(P as i64) as u32))
+    }
+}
+
+impl<const P: u32> Field for Fp32<P> {
+    fn inv(self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        // Extended Euclid, only tracking the coefficient of self
+        let (mut r0, mut r1) = (P as i64, self.0 as i64);
+        let (mut t0, mut t1) = (0i64, 1i64);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        Some(Fp32(t0.rem_euclid(P as i64) as u32))
+    }
+
+    fn get_characteristic() -> usize {
+        P as usize
+    }
+
+    fn as_usize(self) -> usize {
+        self.0 as usize
+    }
+}
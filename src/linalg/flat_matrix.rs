@@ -1,8 +1,9 @@
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use super::{
     field::Field,
-    matrix::{Matrix, RModMorphism},
+    matrix::{solution_from_rref, Matrix, RModMorphism, COMPOSE_BLOCK, PARALLEL_COMPOSE_THRESHOLD},
 };
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -21,45 +22,14 @@ impl<F: Field> FlatMatrix<F> {
         self.data[row * self.domain + col] = value;
     }
 
-    fn rref_kernel(&self) -> Self {
-        let mut free_vars = Vec::new();
-        let pivot_cols: Vec<usize> = self.pivots().iter().map(|x| x.0).collect();
-
-        for j in 0..self.domain {
-            if !pivot_cols.contains(&j) {
-                free_vars.push(j);
-            }
-        }
-
-        let mut kernel = vec![F::zero(); free_vars.len() * self.domain];
-
-        for (i, &free_var) in free_vars.iter().enumerate() {
-            let row_idx = i * self.domain;
-            kernel[row_idx + free_var] = F::one();
-
-            for (row, &pivot_col) in pivot_cols.iter().enumerate() {
-                kernel[row_idx + pivot_col] = -self.get_element(row, free_var);
-            }
-        }
-
-        Self {
-            data: kernel,
-            domain: self.domain,
-            codomain: free_vars.len(),
-        }
-    }
-}
-
-impl<F: Field> Matrix<F> for FlatMatrix<F> {
-    fn kernel(&self) -> Self {
-        let mut clone = self.clone();
-        clone.rref();
-        let mut kernel = clone.rref_kernel();
-        kernel.rref();
-        kernel
+    fn row_slice(&self, row: usize) -> &[F] {
+        let start = row * self.domain;
+        &self.data[start..start + self.domain]
     }
 
-    fn rref(&mut self) {
+    /// Row reduces self, applying every row operation to `transform`
+    /// and its inverse as a column operation to `inverse`.
+    fn rref_tracked(&mut self, mut track: Option<(&mut Self, &mut Self)>) {
         let mut lead = 0;
 
         for r in 0..self.codomain {
@@ -82,6 +52,10 @@ impl<F: Field> Matrix<F> for FlatMatrix<F> {
             for j in 0..self.domain {
                 self.data.swap(r * self.domain + j, i * self.domain + j);
             }
+            if let Some((t, t_inv)) = track.as_mut() {
+                t.swap_rows(r, i);
+                t_inv.swap_columns(r, i);
+            }
 
             let pivot = self.get_element(r, lead);
             if !pivot.is_zero() {
@@ -90,6 +64,10 @@ impl<F: Field> Matrix<F> for FlatMatrix<F> {
                     let idx = r * self.domain + j;
                     self.data[idx] *= pivot_inv;
                 }
+                if let Some((t, t_inv)) = track.as_mut() {
+                    t.scale_row(r, pivot_inv);
+                    t_inv.scale_column(r, pivot);
+                }
             }
 
             for i in 0..self.codomain {
@@ -100,6 +78,13 @@ impl<F: Field> Matrix<F> for FlatMatrix<F> {
                         let el = self.get_element(r, j);
                         self.data[idx] -= factor * el;
                     }
+                    if let Some((t, t_inv)) = track.as_mut() {
+                        if !factor.is_zero() {
+                            // row_i -= factor * row_r is undone by col_r += factor * col_i
+                            t.add_row_multiple(r, i, -factor);
+                            t_inv.add_column_multiple(i, r, factor);
+                        }
+                    }
                 }
             }
 
@@ -107,6 +92,153 @@ impl<F: Field> Matrix<F> for FlatMatrix<F> {
         }
     }
 
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for j in 0..self.domain {
+            self.data.swap(a * self.domain + j, b * self.domain + j);
+        }
+    }
+
+    fn swap_columns(&mut self, a: usize, b: usize) {
+        for i in 0..self.codomain {
+            self.data.swap(i * self.domain + a, i * self.domain + b);
+        }
+    }
+
+    fn scale_row(&mut self, row: usize, factor: F) {
+        for j in 0..self.domain {
+            self.data[row * self.domain + j] *= factor;
+        }
+    }
+
+    fn scale_column(&mut self, col: usize, factor: F) {
+        for i in 0..self.codomain {
+            self.data[i * self.domain + col] *= factor;
+        }
+    }
+
+    /// row target += factor * row source
+    fn add_row_multiple(&mut self, source: usize, target: usize, factor: F) {
+        for j in 0..self.domain {
+            let el = self.get_element(source, j);
+            self.data[target * self.domain + j] += factor * el;
+        }
+    }
+
+    /// column target += factor * column source
+    fn add_column_multiple(&mut self, source: usize, target: usize, factor: F) {
+        for i in 0..self.codomain {
+            let el = self.get_element(i, source);
+            self.data[i * self.domain + target] += factor * el;
+        }
+    }
+
+    /// Multiplies tile by tile, every block of output rows in parallel
+    fn compose_blocked(&self, rhs: &Self) -> Self {
+        let width = rhs.domain;
+        if width == 0 {
+            return Self::zero(width, self.codomain);
+        }
+        let mut compose = vec![F::zero(); self.codomain * width];
+
+        compose
+            .par_chunks_mut(COMPOSE_BLOCK * width)
+            .enumerate()
+            .for_each(|(block, out)| {
+                let first_row = block * COMPOSE_BLOCK;
+                let rows = out.len() / width;
+                for k_start in (0..self.domain).step_by(COMPOSE_BLOCK) {
+                    let k_end = (k_start + COMPOSE_BLOCK).min(self.domain);
+                    for j_start in (0..width).step_by(COMPOSE_BLOCK) {
+                        let j_end = (j_start + COMPOSE_BLOCK).min(width);
+                        for r in 0..rows {
+                            let out_row = &mut out[r * width + j_start..r * width + j_end];
+                            for k in k_start..k_end {
+                                let a = self.get_element(first_row + r, k);
+                                if a.is_zero() {
+                                    continue;
+                                }
+                                let rhs_row = &rhs.data[k * width + j_start..k * width + j_end];
+                                for (o, b) in out_row.iter_mut().zip(rhs_row) {
+                                    *o += a * *b;
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+
+        Self {
+            data: compose,
+            domain: width,
+            codomain: self.codomain,
+        }
+    }
+
+    fn rref_kernel(&self) -> Self {
+        let mut free_vars = Vec::new();
+        let pivot_cols: Vec<usize> = self.pivots().iter().map(|x| x.0).collect();
+
+        for j in 0..self.domain {
+            if !pivot_cols.contains(&j) {
+                free_vars.push(j);
+            }
+        }
+
+        let mut kernel = vec![F::zero(); free_vars.len() * self.domain];
+
+        for (i, &free_var) in free_vars.iter().enumerate() {
+            let row_idx = i * self.domain;
+            kernel[row_idx + free_var] = F::one();
+
+            for (row, &pivot_col) in pivot_cols.iter().enumerate() {
+                kernel[row_idx + pivot_col] = -self.get_element(row, free_var);
+            }
+        }
+
+        Self {
+            data: kernel,
+            domain: self.domain,
+            codomain: free_vars.len(),
+        }
+    }
+}
+
+impl<F: Field> Matrix<F> for FlatMatrix<F> {
+    fn kernel(&self) -> Self {
+        let mut clone = self.clone();
+        clone.rref();
+        let mut kernel = clone.rref_kernel();
+        kernel.rref();
+        kernel
+    }
+
+    fn rref(&mut self) {
+        self.rref_tracked(None);
+    }
+
+    fn rref_with_transform(&mut self) -> (Self, Self) {
+        let mut transform = Self::identity(self.codomain);
+        let mut inverse = Self::identity(self.codomain);
+        self.rref_tracked(Some((&mut transform, &mut inverse)));
+        (transform, inverse)
+    }
+
+    fn solve(&self, v: &[F]) -> Option<Vec<F>> {
+        debug_assert_eq!(v.len(), self.codomain, "Vector is not in the codomain");
+        let mut data = Vec::with_capacity((self.domain + 1) * self.codomain);
+        for (i, x) in v.iter().enumerate() {
+            data.extend_from_slice(self.row_slice(i));
+            data.push(*x);
+        }
+        let mut augmented = Self {
+            data,
+            domain: self.domain + 1,
+            codomain: self.codomain,
+        };
+        augmented.rref();
+        solution_from_rref(&augmented)
+    }
+
     fn pivots(&self) -> Vec<(usize, usize)> {
         let mut col = 0;
         let mut pivots = vec![];
@@ -169,12 +301,12 @@ impl<F: Field> RModMorphism<F> for FlatMatrix<F> {
 
         for i in 0..self.codomain {
             let start = i * new_domain;
-            new.data[start..(start + self.domain)].copy_from_slice(self.get_row(i));
+            new.data[start..(start + self.domain)].copy_from_slice(self.row_slice(i));
         }
 
         for i in 0..other.codomain {
             let start = (self.codomain + i) * new_domain + self.domain;
-            new.data[start..(start + other.domain)].copy_from_slice(other.get_row(i));
+            new.data[start..(start + other.domain)].copy_from_slice(other.row_slice(i));
         }
 
         *self = new
@@ -205,10 +337,13 @@ impl<F: Field> RModMorphism<F> for FlatMatrix<F> {
         self.data[idx] += f;
     }
 
+    type Row<'a>
+        = &'a [F]
+    where
+        F: 'a;
+
     fn get_row(&self, codomain: usize) -> &[F] {
-        let start = codomain * self.domain;
-        let end = start + self.domain;
-        &self.data[start..end]
+        self.row_slice(codomain)
     }
 
     fn set_row(&mut self, codomain: usize, row: &[F]) {
@@ -231,6 +366,10 @@ impl<F: Field> RModMorphism<F> for FlatMatrix<F> {
             "Matrix domain not equal to codomain"
         );
 
+        if self.codomain * self.domain * rhs.domain >= PARALLEL_COMPOSE_THRESHOLD {
+            return self.compose_blocked(rhs);
+        }
+
         let mut compose = vec![F::zero(); self.codomain * rhs.domain];
 
         for x in 0..self.codomain {
@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    field::{Field, Fp},
+    flat_matrix::FlatMatrix,
+    matrix::{Matrix, RModMorphism},
+};
+
+/// Dense matrix over `Fp<P>` which stores its entries as `u32` and delays the modular reduction.
+///
+/// Outside of `rref` and `compose` every entry is reduced, so equality is entry-wise.
+/// Inside them sums are accumulated unreduced and only reduced when they could overflow,
+/// instead of doing a `% P` for every single addition like `Fp<P>` does.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FpMatrix<const P: u8> {
+    pub data: Vec<u32>,
+    pub domain: usize,
+    pub codomain: usize,
+}
+
+impl<const P: u8> FpMatrix<P> {
+    /// How many `row += c * pivot_row` updates fit in a `u32` entry before it has to be reduced
+    const MAX_PENDING: u32 = (u32::MAX - P as u32) / ((P as u32 - 1) * (P as u32 - 1));
+
+    fn row(&self, row: usize) -> &[u32] {
+        &self.data[row * self.domain..(row + 1) * self.domain]
+    }
+
+    fn reduce_row(&mut self, row: usize) {
+        for x in self.data[row * self.domain..(row + 1) * self.domain].iter_mut() {
+            *x %= P as u32;
+        }
+    }
+
+    /// Reduces a single entry in place and returns it
+    fn reduced(&mut self, row: usize, col: usize) -> u32 {
+        let idx = row * self.domain + col;
+        self.data[idx] %= P as u32;
+        self.data[idx]
+    }
+
+    fn rref_kernel(&self) -> Self {
+        let pivots = self.pivots();
+        let mut is_pivot = vec![false; self.domain];
+        for &(col, _) in &pivots {
+            is_pivot[col] = true;
+        }
+        let free_vars: Vec<usize> = (0..self.domain).filter(|&j| !is_pivot[j]).collect();
+
+        let mut kernel = Self::zero(self.domain, free_vars.len());
+        for (i, &free_var) in free_vars.iter().enumerate() {
+            let row_idx = i * self.domain;
+            kernel.data[row_idx + free_var] = 1;
+
+            for &(pivot_col, row) in &pivots {
+                let x = self.data[row * self.domain + free_var];
+                kernel.data[row_idx + pivot_col] = (P as u32 - x) % P as u32;
+            }
+        }
+        kernel
+    }
+}
+
+impl<const P: u8> Matrix<Fp<P>> for FpMatrix<P> {
+    fn kernel(&self) -> Self {
+        let mut clone = self.clone();
+        clone.rref();
+        let mut kernel = clone.rref_kernel();
+        kernel.rref();
+        kernel
+    }
+
+    fn rref(&mut self) {
+        let p = P as u32;
+        // Number of unreduced updates every row has received
+        let mut pending = vec![0u32; self.codomain];
+        let mut lead = 0;
+
+        for r in 0..self.codomain {
+            if lead >= self.domain {
+                break;
+            }
+
+            let pivot_row = loop {
+                match (r..self.codomain).find(|&i| self.reduced(i, lead) != 0) {
+                    Some(i) => break i,
+                    None => {
+                        lead += 1;
+                        if lead == self.domain {
+                            self.data.iter_mut().for_each(|x| *x %= p);
+                            return;
+                        }
+                    }
+                }
+            };
+
+            if pivot_row != r {
+                for j in 0..self.domain {
+                    self.data
+                        .swap(r * self.domain + j, pivot_row * self.domain + j);
+                }
+                pending.swap(r, pivot_row);
+            }
+
+            // The pivot row is used as a multiplicand, so it has to be fully reduced
+            let pivot_inv = Fp::<P>(self.data[r * self.domain + lead] as u8)
+                .inv()
+                .expect("Pivot should be invertible")
+                .0 as u32;
+            for x in self.data[r * self.domain..(r + 1) * self.domain].iter_mut() {
+                *x = (*x % p) * pivot_inv % p;
+            }
+            pending[r] = 0;
+
+            for i in 0..self.codomain {
+                if i == r {
+                    continue;
+                }
+                let factor = self.reduced(i, lead);
+                if factor == 0 {
+                    continue;
+                }
+                // Subtracting factor * pivot_row is adding (p - factor) * pivot_row
+                let c = p - factor;
+                let (pivot, target) = if r < i {
+                    let (a, b) = self.data.split_at_mut(i * self.domain);
+                    (
+                        &a[r * self.domain..(r + 1) * self.domain],
+                        &mut b[..self.domain],
+                    )
+                } else {
+                    let (a, b) = self.data.split_at_mut(r * self.domain);
+                    (
+                        &b[..self.domain],
+                        &mut a[i * self.domain..(i + 1) * self.domain],
+                    )
+                };
+                // Entries left of the lead column are zero in the pivot row
+                for (t, &x) in target[lead..].iter_mut().zip(pivot[lead..].iter()) {
+                    *t += c * x;
+                }
+                pending[i] += 1;
+                if pending[i] == Self::MAX_PENDING {
+                    self.reduce_row(i);
+                    pending[i] = 0;
+                }
+            }
+
+            lead += 1;
+        }
+
+        self.data.iter_mut().for_each(|x| *x %= p);
+    }
+
+    fn pivots(&self) -> Vec<(usize, usize)> {
+        let mut col = 0;
+        let mut pivots = vec![];
+        for row in 0..self.codomain {
+            while col < self.domain {
+                if self.data[row * self.domain + col] != 0 {
+                    pivots.push((col, row));
+                    col += 1;
+                    break;
+                }
+                col += 1;
+            }
+        }
+        pivots
+    }
+
+    fn first_non_zero_entry(&self) -> Option<(usize, usize)> {
+        (0..self.codomain).find_map(|codom_id| {
+            self.row(codom_id)
+                .iter()
+                .position(|&x| x != 0)
+                .map(|dom_id| (codom_id, dom_id))
+        })
+    }
+}
+
+impl<const P: u8> RModMorphism<Fp<P>> for FpMatrix<P> {
+    fn zero(domain: usize, codomain: usize) -> Self {
+        Self {
+            data: vec![0; domain * codomain],
+            domain,
+            codomain,
+        }
+    }
+
+    fn identity(d: usize) -> Self {
+        let mut id = Self::zero(d, d);
+        for i in 0..d {
+            id.data[i * d + i] = 1;
+        }
+        id
+    }
+
+    fn get(&self, domain: usize, codomain: usize) -> Fp<P> {
+        Fp(self.data[codomain * self.domain + domain] as u8)
+    }
+
+    fn set(&mut self, domain: usize, codomain: usize, f: Fp<P>) {
+        self.data[codomain * self.domain + domain] = f.0 as u32;
+    }
+
+    fn add_at(&mut self, domain: usize, codomain: usize, f: Fp<P>) {
+        let idx = codomain * self.domain + domain;
+        self.data[idx] = (self.data[idx] + f.0 as u32) % P as u32;
+    }
+
+    type Row<'a> = Vec<Fp<P>>;
+
+    fn get_row(&self, codomain: usize) -> Vec<Fp<P>> {
+        self.row(codomain).iter().map(|&x| Fp(x as u8)).collect()
+    }
+
+    fn set_row(&mut self, codomain: usize, row: &[Fp<P>]) {
+        let start = codomain * self.domain;
+        for (t, f) in self.data[start..start + self.domain].iter_mut().zip(row) {
+            *t = f.0 as u32;
+        }
+    }
+
+    fn is_row_non_zero(&self, codomain: usize) -> bool {
+        self.row(codomain).iter().any(|&x| x != 0)
+    }
+
+    // domain l == codomain r, l \circ r
+    fn compose(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(
+            self.domain, rhs.codomain,
+            "Matrix domain not equal to codomain"
+        );
+
+        // Every product is below P^2 < 2^16, so a u64 accumulator never overflows in practice
+        let trans = rhs.transpose();
+        let mut compose = Self::zero(rhs.domain, self.codomain);
+        for x in 0..self.codomain {
+            let l_row = self.row(x);
+            for y in 0..rhs.domain {
+                let sum: u64 = l_row
+                    .iter()
+                    .zip(trans.row(y))
+                    .map(|(&a, &b)| a as u64 * b as u64)
+                    .sum();
+                compose.data[x * rhs.domain + y] = (sum % P as u64) as u32;
+            }
+        }
+        compose
+    }
+
+    fn transpose(&self) -> Self {
+        let mut trans = Self::zero(self.codomain, self.domain);
+        for i in 0..self.codomain {
+            for j in 0..self.domain {
+                trans.data[j * self.codomain + i] = self.data[i * self.domain + j];
+            }
+        }
+        trans
+    }
+
+    fn domain(&self) -> usize {
+        self.domain
+    }
+
+    fn codomain(&self) -> usize {
+        self.codomain
+    }
+
+    fn vstack(&mut self, other: &mut Self) {
+        debug_assert_eq!(
+            self.domain(),
+            other.domain(),
+            "Domains of the two matrices do not have the same dimension"
+        );
+
+        self.data.append(&mut other.data);
+        self.codomain += other.codomain;
+    }
+
+    fn block_sum(&mut self, other: &Self) {
+        let new_domain = self.domain + other.domain;
+        let mut new = Self::zero(new_domain, self.codomain + other.codomain);
+
+        for i in 0..self.codomain {
+            let start = i * new_domain;
+            new.data[start..start + self.domain].copy_from_slice(self.row(i));
+        }
+
+        for i in 0..other.codomain {
+            let start = (self.codomain + i) * new_domain + self.domain;
+            new.data[start..start + other.domain].copy_from_slice(other.row(i));
+        }
+
+        *self = new
+    }
+}
+
+impl<const P: u8> From<&FlatMatrix<Fp<P>>> for FpMatrix<P> {
+    fn from(value: &FlatMatrix<Fp<P>>) -> Self {
+        Self {
+            data: value.data.iter().map(|x| x.0 as u32).collect(),
+            domain: value.domain,
+            codomain: value.codomain,
+        }
+    }
+}
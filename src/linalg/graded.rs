@@ -1,12 +1,13 @@
-use std::{collections::HashMap, fmt::Debug, marker::PhantomData};
+use std::{collections::BTreeMap, fmt::Debug, marker::PhantomData};
 
-use ahash::RandomState;
 use rayon::prelude::*;
 
 use super::{
-    field::Field,
+    field::{CRing, Field},
     grading::Grading,
     matrix::{Matrix, RModMorphism},
+    ring::PrincipalIdealRing,
+    ring_matrix::{RingCokernel, RingKernel, RingMatrix},
 };
 use serde::{Deserialize, Serialize};
 
@@ -15,35 +16,51 @@ pub trait BasisElement: 'static + Debug + Clone {}
 pub type BasisIndex<G> = (G, usize);
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct GradedVectorSpace<G: Grading, B: BasisElement>(pub HashMap<G, Vec<B>, RandomState>);
+pub struct GradedVectorSpace<G: Grading, B: BasisElement>(pub BTreeMap<G, Vec<B>>);
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct GradedLinearMap<G: Grading, F: Field, M: Matrix<F>> {
-    pub maps: HashMap<G, M, RandomState>,
-    __: PhantomData<F>,
+pub struct GradedLinearMap<G: Grading, R: CRing, M: RModMorphism<R>> {
+    pub maps: BTreeMap<G, M>,
+    __: PhantomData<R>,
+}
+
+/// Subspace of `ambient`, carrying its inclusion into it.
+///
+/// Every basis element is a copy of the ambient element of its leading coordinate.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradedSubspace<G: Grading, F: Field, M: Matrix<F>, B: BasisElement> {
+    pub ambient: GradedVectorSpace<G, B>,
+    pub space: GradedVectorSpace<G, B>,
+    pub inclusion: GradedLinearMap<G, F, M>,
+}
+
+/// Quotient of `ambient`, carrying the projection onto it.
+///
+/// Every basis element is a copy of the ambient element which survives as it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradedQuotient<G: Grading, F: Field, M: Matrix<F>, B: BasisElement> {
+    pub ambient: GradedVectorSpace<G, B>,
+    pub space: GradedVectorSpace<G, B>,
+    pub projection: GradedLinearMap<G, F, M>,
 }
 
 impl<G: Grading, B: BasisElement> GradedVectorSpace<G, B> {
     pub fn new() -> Self {
-        Self(HashMap::default())
+        Self(BTreeMap::new())
     }
     pub fn dimension_in_grade(&self, grade: &G) -> usize {
         self.0.get(grade).map(|x| x.len()).unwrap_or(0)
     }
 }
 
-impl<G: Grading, B: BasisElement> From<HashMap<G, Vec<B>, RandomState>>
-    for GradedVectorSpace<G, B>
-{
-    fn from(value: HashMap<G, Vec<B>, RandomState>) -> Self {
+impl<G: Grading, B: BasisElement> From<BTreeMap<G, Vec<B>>> for GradedVectorSpace<G, B> {
+    fn from(value: BTreeMap<G, Vec<B>>) -> Self {
         Self(value)
     }
 }
 
-impl<G: Grading, F: Field, M: Matrix<F>> From<HashMap<G, M, RandomState>>
-    for GradedLinearMap<G, F, M>
-{
-    fn from(value: HashMap<G, M, RandomState>) -> Self {
+impl<G: Grading, R: CRing, M: RModMorphism<R>> From<BTreeMap<G, M>> for GradedLinearMap<G, R, M> {
+    fn from(value: BTreeMap<G, M>) -> Self {
         Self {
             maps: value,
             __: PhantomData,
@@ -51,31 +68,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> From<HashMap<G, M, RandomState>>
     }
 }
 
-impl<G: Grading, F: Field, M: Matrix<F>> GradedLinearMap<G, F, M> {
-    pub fn get_cokernel(&self) -> Self {
-        let cokernel = self
-            .maps
-            .par_iter()
-            .map(|(k, v)| (*k, v.cokernel()))
-            .collect();
-        GradedLinearMap {
-            maps: cokernel,
-            __: PhantomData,
-        }
-    }
-
-    pub fn get_kernel(&self) -> Self {
-        let kernel = self
-            .maps
-            .par_iter()
-            .map(|(k, v)| (*k, v.kernel()))
-            .collect();
-        GradedLinearMap {
-            maps: kernel,
-            __: PhantomData,
-        }
-    }
-
+impl<G: Grading, R: CRing, M: RModMorphism<R> + Clone + Send + Sync> GradedLinearMap<G, R, M> {
     pub fn vstack(&mut self, other: &mut Self) {
         other.maps.iter_mut().for_each(|(grade, other_mat)| {
             self.maps
@@ -96,15 +89,15 @@ impl<G: Grading, F: Field, M: Matrix<F>> GradedLinearMap<G, F, M> {
                 }
                 None => {}
             });
-        other.maps.drain().for_each(|(g, map)| {
-            if !self.maps.contains_key(&g) {
-                self.maps.insert(g, map);
-            }
-        });
+        std::mem::take(&mut other.maps)
+            .into_iter()
+            .for_each(|(g, map)| {
+                self.maps.entry(g).or_insert(map);
+            });
     }
 
     pub fn compose(&self, rhs: &Self) -> Self {
-        let mut compose: HashMap<G, M, RandomState> = self
+        let mut compose: BTreeMap<G, M> = self
             .maps
             .par_iter()
             .filter_map(|(k, v)| match rhs.maps.get(&k) {
@@ -131,16 +124,9 @@ impl<G: Grading, F: Field, M: Matrix<F>> GradedLinearMap<G, F, M> {
         }
     }
 
-    pub fn pivots(&self) -> HashMap<G, Vec<(usize, usize)>> {
-        self.maps
-            .par_iter()
-            .map(|(k, v)| (*k, v.pivots()))
-            .collect()
-    }
-
     pub fn empty() -> Self {
         GradedLinearMap {
-            maps: HashMap::default(),
+            maps: BTreeMap::new(),
             __: PhantomData,
         }
     }
@@ -149,7 +135,7 @@ impl<G: Grading, F: Field, M: Matrix<F>> GradedLinearMap<G, F, M> {
         domain: &GradedVectorSpace<G, B>,
         codomain: &GradedVectorSpace<G, B>,
     ) -> Self {
-        let mut maps: HashMap<G, M, RandomState> = domain
+        let mut maps: BTreeMap<G, M> = domain
             .0
             .iter()
             .map(|(g, els)| {
@@ -192,3 +178,293 @@ impl<G: Grading, F: Field, M: Matrix<F>> GradedLinearMap<G, F, M> {
         GradedVectorSpace(space)
     }
 }
+
+impl<G: Grading, F: Field, M: Matrix<F>> GradedLinearMap<G, F, M> {
+    pub fn get_cokernel(&self) -> Self {
+        let cokernel = self
+            .maps
+            .par_iter()
+            .map(|(k, v)| (*k, v.cokernel()))
+            .collect();
+        GradedLinearMap {
+            maps: cokernel,
+            __: PhantomData,
+        }
+    }
+
+    pub fn get_kernel(&self) -> Self {
+        let kernel = self
+            .maps
+            .par_iter()
+            .map(|(k, v)| (*k, v.kernel()))
+            .collect();
+        GradedLinearMap {
+            maps: kernel,
+            __: PhantomData,
+        }
+    }
+
+    pub fn pivots(&self) -> BTreeMap<G, Vec<(usize, usize)>> {
+        self.maps
+            .par_iter()
+            .map(|(k, v)| (*k, v.pivots()))
+            .collect()
+    }
+
+    pub fn get_image(&self) -> Self {
+        let image = self.maps.par_iter().map(|(k, v)| (*k, v.image())).collect();
+        GradedLinearMap {
+            maps: image,
+            __: PhantomData,
+        }
+    }
+
+    pub fn ranks(&self) -> BTreeMap<G, usize> {
+        self.maps.par_iter().map(|(k, v)| (*k, v.rank())).collect()
+    }
+
+    /// Grade-wise preimage of a graded vector, grades missing in `v` are taken to be zero
+    pub fn solve(&self, v: &BTreeMap<G, Vec<F>>) -> Option<BTreeMap<G, Vec<F>>> {
+        let mut solution = BTreeMap::new();
+        for (grade, vector) in v {
+            match self.maps.get(grade) {
+                Some(m) => {
+                    solution.insert(*grade, m.solve(vector)?);
+                }
+                None => {
+                    if vector.iter().any(|x| !x.is_zero()) {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(solution)
+    }
+
+    pub fn is_in_image(&self, v: &BTreeMap<G, Vec<F>>) -> bool {
+        v.par_iter()
+            .all(|(grade, vector)| match self.maps.get(grade) {
+                Some(m) => m.is_in_image(vector),
+                None => vector.iter().all(|x| x.is_zero()),
+            })
+    }
+
+    /// Grade-wise canonical representative of `v` modulo the image
+    pub fn reduce_modulo_image(&self, v: &BTreeMap<G, Vec<F>>) -> BTreeMap<G, Vec<F>> {
+        v.par_iter()
+            .map(|(grade, vector)| match self.maps.get(grade) {
+                Some(m) => (*grade, m.reduce_modulo_image(vector)),
+                None => (*grade, vector.clone()),
+            })
+            .collect()
+    }
+}
+
+impl<G: Grading, F: Field, M: Matrix<F>> GradedLinearMap<G, F, M> {
+    /// The map in grade `g`, zero if it is missing
+    fn get_or_zero(&self, g: &G, domain: usize, codomain: usize) -> M {
+        self.maps
+            .get(g)
+            .cloned()
+            .unwrap_or_else(|| M::zero(domain, codomain))
+    }
+
+    /// Kernel as a subspace of `domain`
+    pub fn kernel_subspace<B: BasisElement>(
+        &self,
+        domain: &GradedVectorSpace<G, B>,
+    ) -> GradedSubspace<G, F, M, B> {
+        let rows = domain
+            .0
+            .iter()
+            .map(|(g, els)| (*g, self.get_or_zero(g, els.len(), 0).kernel()))
+            .collect();
+        GradedSubspace::from_rows(domain, rows)
+    }
+
+    /// Image as a subspace of `codomain`
+    pub fn image_subspace<B: BasisElement>(
+        &self,
+        codomain: &GradedVectorSpace<G, B>,
+    ) -> GradedSubspace<G, F, M, B> {
+        let rows = codomain
+            .0
+            .iter()
+            .map(|(g, els)| (*g, self.get_or_zero(g, 0, els.len()).image()))
+            .collect();
+        GradedSubspace::from_rows(codomain, rows)
+    }
+
+    /// Cokernel as a quotient of `codomain`
+    pub fn cokernel_quotient<B: BasisElement>(
+        &self,
+        codomain: &GradedVectorSpace<G, B>,
+    ) -> GradedQuotient<G, F, M, B> {
+        let projection: BTreeMap<G, M> = codomain
+            .0
+            .iter()
+            .map(|(g, els)| (*g, self.get_or_zero(g, 0, els.len()).cokernel()))
+            .collect();
+        GradedQuotient::new(codomain, GradedLinearMap::from(projection))
+    }
+}
+
+impl<G: Grading, F: Field, M: Matrix<F>, B: BasisElement> GradedSubspace<G, F, M, B> {
+    /// Subspace spanned by the rows of `rows`, which are vectors in `ambient`
+    fn from_rows(ambient: &GradedVectorSpace<G, B>, rows: BTreeMap<G, M>) -> Self {
+        let mut space = BTreeMap::new();
+        let mut inclusion = BTreeMap::new();
+        for (g, els) in &ambient.0 {
+            let rows = rows
+                .get(g)
+                .cloned()
+                .unwrap_or_else(|| M::zero(els.len(), 0));
+            let basis: Vec<B> = (0..rows.codomain())
+                .map(|i| {
+                    let leading = rows
+                        .get_row(i)
+                        .iter()
+                        .position(|x| !x.is_zero())
+                        .expect("Spanning vectors are non-zero");
+                    els[leading].clone()
+                })
+                .collect();
+            if !basis.is_empty() {
+                space.insert(*g, basis);
+            }
+            inclusion.insert(*g, rows.transpose());
+        }
+        Self {
+            ambient: ambient.clone(),
+            space: GradedVectorSpace(space),
+            inclusion: GradedLinearMap::from(inclusion),
+        }
+    }
+
+    /// Subspace spanned by the images of `inclusion`, which does not have to be injective
+    pub fn new(ambient: &GradedVectorSpace<G, B>, inclusion: &GradedLinearMap<G, F, M>) -> Self {
+        inclusion.image_subspace(ambient)
+    }
+
+    pub fn dimension_in_grade(&self, grade: &G) -> usize {
+        self.space.dimension_in_grade(grade)
+    }
+
+    /// Whether every grade of `v` lies in the subspace
+    pub fn contains(&self, v: &BTreeMap<G, Vec<F>>) -> bool {
+        self.inclusion.is_in_image(v)
+    }
+
+    /// Fails unless both subspaces live in ambient spaces of the same dimensions
+    fn check_ambient(&self, other: &Self) -> Result<(), String> {
+        for g in self.ambient.0.keys().chain(other.ambient.0.keys()) {
+            let (l, r) = (
+                self.ambient.dimension_in_grade(g),
+                other.ambient.dimension_in_grade(g),
+            );
+            if l != r {
+                return Err(format!(
+                    "Different ambient spaces, dimension {} and {} in grade {}",
+                    l, r, g
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Inclusion in grade `g`, grades without one are the zero subspace
+    fn inclusion_in_grade(&self, g: &G) -> M {
+        self.inclusion
+            .get_or_zero(g, 0, self.ambient.dimension_in_grade(g))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Result<Self, String> {
+        self.check_ambient(other)?;
+        let rows = self
+            .ambient
+            .0
+            .keys()
+            .map(|g| {
+                // Combinations of self which vanish modulo other
+                let (u, w) = (self.inclusion_in_grade(g), other.inclusion_in_grade(g));
+                let coordinates = w.cokernel().compose(&u).kernel().transpose();
+                (*g, u.compose(&coordinates).transpose())
+            })
+            .collect();
+        Ok(Self::from_rows(&self.ambient, rows))
+    }
+
+    pub fn sum(&self, other: &Self) -> Result<Self, String> {
+        self.check_ambient(other)?;
+        let rows = self
+            .ambient
+            .0
+            .keys()
+            .map(|g| {
+                let mut spanning = self.inclusion_in_grade(g).transpose();
+                spanning.vstack(&mut other.inclusion_in_grade(g).transpose());
+                (*g, spanning.transpose().image())
+            })
+            .collect();
+        Ok(Self::from_rows(&self.ambient, rows))
+    }
+
+    /// The ambient space modulo this subspace
+    pub fn quotient(&self) -> GradedQuotient<G, F, M, B> {
+        self.inclusion.cokernel_quotient(&self.ambient)
+    }
+}
+
+impl<G: Grading, F: Field, M: Matrix<F>, B: BasisElement> GradedQuotient<G, F, M, B> {
+    /// Quotient of `ambient` along the surjection `projection`
+    pub fn new(ambient: &GradedVectorSpace<G, B>, projection: GradedLinearMap<G, F, M>) -> Self {
+        let space = projection
+            .maps
+            .iter()
+            .filter(|(_, m)| m.codomain() > 0)
+            .map(|(g, m)| {
+                let mut basis = vec![None; m.codomain()];
+                for (ambient_id, quotient_id) in m.pivots() {
+                    basis[quotient_id] = Some(ambient.0[g][ambient_id].clone());
+                }
+                let basis = basis
+                    .into_iter()
+                    .map(|b| b.expect("Projection is surjective"))
+                    .collect();
+                (*g, basis)
+            })
+            .collect();
+        Self {
+            ambient: ambient.clone(),
+            space: GradedVectorSpace(space),
+            projection,
+        }
+    }
+
+    pub fn dimension_in_grade(&self, grade: &G) -> usize {
+        self.space.dimension_in_grade(grade)
+    }
+
+    /// The subspace which is divided out
+    pub fn kernel(&self) -> GradedSubspace<G, F, M, B> {
+        self.projection.kernel_subspace(&self.ambient)
+    }
+}
+
+impl<G: Grading, R: PrincipalIdealRing> GradedLinearMap<G, R, RingMatrix<R>> {
+    /// Grade-wise kernel, keeping the torsion of every generator
+    pub fn get_ring_kernel(&self) -> BTreeMap<G, RingKernel<R>> {
+        self.maps
+            .par_iter()
+            .map(|(k, v)| (*k, v.kernel()))
+            .collect()
+    }
+
+    /// Grade-wise cokernel with its invariant factors
+    pub fn get_ring_cokernel(&self) -> BTreeMap<G, RingCokernel<R>> {
+        self.maps
+            .par_iter()
+            .map(|(k, v)| (*k, v.cokernel()))
+            .collect()
+    }
+}
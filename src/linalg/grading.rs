@@ -31,12 +31,29 @@ pub trait Grading:
     fn default_formulas() -> (String, String);
     fn export_grade(self) -> Vec<i32>;
 
-    fn incr(self) -> Self;
+    /// Whether this grade lies within the truncation bounded by `limit`.
+    ///
+    /// This is used for truncating instead of `Ord`, which only has to be a linear
+    /// extension of it so grades can be sorted.
+    fn within(self, limit: Self) -> bool;
+
+    /// Whether this grade lies strictly above zero for `within`.
+    ///
+    /// Powers of a generator in such a grade eventually leave every truncation, and a
+    /// product is only within a limit if its factors are.
+    fn is_positive(self) -> bool {
+        Self::zero().within(self) && !self.within(Self::zero())
+    }
+
+    /// Parity of the topological degree, which decides the Koszul signs
+    fn is_odd(self) -> bool;
+
     fn zero() -> Self;
     fn infty() -> Self;
 
     fn integer_multiplication(self, other: i32) -> Self;
 
+    /// Parses a grade from its degrees, separated by commas in the order of `degree_names`
     fn parse(parse: &str) -> Result<Self, String>;
 }
 
@@ -53,6 +70,14 @@ impl Grading for i32 {
         vec![self]
     }
 
+    fn within(self, limit: Self) -> bool {
+        self <= limit
+    }
+
+    fn is_odd(self) -> bool {
+        self % 2 != 0
+    }
+
     fn zero() -> Self {
         0
     }
@@ -61,10 +86,6 @@ impl Grading for i32 {
         i32::MAX
     }
 
-    fn incr(self) -> Self {
-        self + 1
-    }
-
     fn parse(parse: &str) -> Result<Self, String> {
         i32::from_str(parse).map_err(|_| format!("Grade: {} could not be parsed", parse))
     }
@@ -151,6 +172,15 @@ impl Grading for BiGrading {
         vec![self.0, self.1]
     }
 
+    /// Componentwise, unlike the lexicographic `Ord`
+    fn within(self, limit: Self) -> bool {
+        self.0 <= limit.0 && self.1 <= limit.1
+    }
+
+    fn is_odd(self) -> bool {
+        self.0 % 2 != 0
+    }
+
     fn zero() -> Self {
         BiGrading(0, 0)
     }
@@ -159,10 +189,6 @@ impl Grading for BiGrading {
         BiGrading(i32::MAX, i32::MAX)
     }
 
-    fn incr(self) -> Self {
-        BiGrading(self.0 + 1, self.1 + 1)
-    }
-
     fn parse(parse: &str) -> Result<Self, String> {
         let parts: Vec<&str> = parse.split(',').collect();
         if parts.len() != 2 {
@@ -179,3 +205,289 @@ impl Grading for BiGrading {
         BiGrading(self.0 * other, self.1 * other)
     }
 }
+
+/// Names used for the degrees of a `MultiGrading`, the first `N` are taken
+pub const MULTI_DEGREE_NAMES: [char; 8] = ['t', 'w', 'u', 'v', 'x', 'y', 'z', 'r'];
+
+/// Grading by `N` integer degrees, e.g. `MultiGrading<3>` for C2-equivariant computations.
+///
+/// Grades are written as `(a, b, c)`, the parentheses are optional.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MultiGrading<const N: usize>(pub [i32; N]);
+
+impl<const N: usize> MultiGrading<N> {
+    const NAMES_FIT: () = assert!(
+        N <= MULTI_DEGREE_NAMES.len(),
+        "MultiGrading has more degrees than names"
+    );
+}
+
+impl<const N: usize> Add for MultiGrading<N> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        self += other;
+        self
+    }
+}
+
+impl<const N: usize> AddAssign for MultiGrading<N> {
+    fn add_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<const N: usize> Sub for MultiGrading<N> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self::Output {
+        self -= other;
+        self
+    }
+}
+
+impl<const N: usize> SubAssign for MultiGrading<N> {
+    fn sub_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<const N: usize> Display for MultiGrading<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let degrees: Vec<String> = self.0.iter().map(|d| d.to_string()).collect();
+        write!(f, "({})", degrees.join(", "))
+    }
+}
+
+impl<const N: usize> FromStr for MultiGrading<N> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as Grading>::parse(s)
+    }
+}
+
+impl<const N: usize> Sum for MultiGrading<N> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(MultiGrading::zero(), |a, b| a + b)
+    }
+}
+
+impl<const N: usize> Grading for MultiGrading<N> {
+    fn degree_names() -> Vec<char> {
+        let () = Self::NAMES_FIT;
+        MULTI_DEGREE_NAMES[..N].to_vec()
+    }
+
+    fn default_formulas() -> (String, String) {
+        ("t-s".to_string(), "s".to_string())
+    }
+
+    fn export_grade(self) -> Vec<i32> {
+        self.0.to_vec()
+    }
+
+    /// Componentwise, unlike the lexicographic `Ord`
+    fn within(self, limit: Self) -> bool {
+        self.0.iter().zip(limit.0).all(|(a, b)| *a <= b)
+    }
+
+    /// Parity of the first degree
+    fn is_odd(self) -> bool {
+        N > 0 && self.0[0] % 2 != 0
+    }
+
+    fn zero() -> Self {
+        MultiGrading([0; N])
+    }
+
+    fn infty() -> Self {
+        MultiGrading([i32::MAX; N])
+    }
+
+    fn parse(parse: &str) -> Result<Self, String> {
+        let err = || format!("Grade: {} could not be parsed", parse);
+        let inner = parse.trim();
+        let inner = match inner.strip_prefix('(') {
+            Some(rest) => rest.strip_suffix(')').ok_or_else(err)?,
+            None => inner,
+        };
+        let parts: Vec<&str> = inner.split(',').collect();
+        if parts.len() != N {
+            return Err(format!(
+                "Grade: {} does not have {} degrees",
+                parse.trim(),
+                N
+            ));
+        }
+        let mut degrees = [0; N];
+        for (d, part) in degrees.iter_mut().zip(parts) {
+            *d = i32::from_str(part.trim()).map_err(|_| err())?;
+        }
+        Ok(MultiGrading(degrees))
+    }
+
+    fn integer_multiplication(self, other: i32) -> Self {
+        MultiGrading(self.0.map(|d| d * other))
+    }
+}
+
+/// How the truncation limit changes between the homological degrees of a resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitPolicy<G: Grading> {
+    /// The same limit in every homological degree
+    Fixed,
+    /// The limit grows by this grade with every homological degree
+    Step(G),
+}
+
+impl<G: Grading> LimitPolicy<G> {
+    /// Limit for the next homological degree
+    pub fn next(&self, limit: G) -> G {
+        match self {
+            LimitPolicy::Fixed => limit,
+            LimitPolicy::Step(step) => limit + *step,
+        }
+    }
+
+    /// Truncation of everything built for a degree at `limit`.
+    ///
+    /// The cokernel of a degree is only injected in the next one and taken again in
+    /// the one after, so cofree comodules and coalgebras reach the limits of the two
+    /// following degrees. With `Fixed` this is `limit` itself.
+    pub fn padded(&self, limit: G) -> G {
+        self.next(self.next(limit))
+    }
+}
+
+impl<G: Grading> Default for LimitPolicy<G> {
+    /// Grows every degree by one
+    fn default() -> Self {
+        let ones = vec!["1"; G::degree_names().len()].join(",");
+        LimitPolicy::Step(G::parse(&ones).expect("A grading parses one integer per degree name"))
+    }
+}
+
+/// Motivic grading by internal degree `t` and weight `w`, written as `(t, w)`.
+///
+/// The parentheses are optional when parsing, e.g. `tau: (0, -1)` or `xi1: 2, 1`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MotivicGrading(pub i32, pub i32);
+
+impl MotivicGrading {
+    pub fn degree(self) -> i32 {
+        self.0
+    }
+
+    pub fn weight(self) -> i32 {
+        self.1
+    }
+
+    /// Internal degree minus weight, which is one for `tau`
+    pub fn coweight(self) -> i32 {
+        self.0 - self.1
+    }
+
+    /// Stem `t - s` against the Chow degree, which is stem plus filtration minus twice
+    /// the weight, so `t - 2w`
+    pub fn chow_formulas() -> (String, String) {
+        ("t-s".to_string(), "t-2w".to_string())
+    }
+}
+
+impl Add for MotivicGrading {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        MotivicGrading(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl AddAssign for MotivicGrading {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+        self.1 += other.1;
+    }
+}
+
+impl Sub for MotivicGrading {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        MotivicGrading(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl SubAssign for MotivicGrading {
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0;
+        self.1 -= other.1;
+    }
+}
+
+impl Display for MotivicGrading {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+impl FromStr for MotivicGrading {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as Grading>::parse(s)
+    }
+}
+
+impl Sum for MotivicGrading {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(MotivicGrading::zero(), |a, b| a + b)
+    }
+}
+
+impl Grading for MotivicGrading {
+    fn degree_names() -> Vec<char> {
+        vec!['t', 'w']
+    }
+
+    fn default_formulas() -> (String, String) {
+        ("t-s".to_string(), "s".to_string())
+    }
+
+    /// Internal degree and weight, the filtration is exported separately
+    fn export_grade(self) -> Vec<i32> {
+        vec![self.0, self.1]
+    }
+
+    /// Bounds the internal degree and the coweight `t - w`, as the weight alone does not
+    /// bound powers of `tau`. Both are non-negative for `tau`, `xi_i` and `tau_i`.
+    fn within(self, limit: Self) -> bool {
+        self.0 <= limit.0 && self.coweight() <= limit.coweight()
+    }
+
+    /// Parity of the internal degree `t`
+    fn is_odd(self) -> bool {
+        self.0 % 2 != 0
+    }
+
+    fn zero() -> Self {
+        MotivicGrading(0, 0)
+    }
+
+    fn infty() -> Self {
+        MotivicGrading(i32::MAX, i32::MAX)
+    }
+
+    fn parse(parse: &str) -> Result<Self, String> {
+        let [t, w] = MultiGrading::<2>::parse(parse)?.0;
+        Ok(MotivicGrading(t, w))
+    }
+
+    fn integer_multiplication(self, other: i32) -> Self {
+        MotivicGrading(self.0 * other, self.1 * other)
+    }
+}
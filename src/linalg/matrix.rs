@@ -1,8 +1,15 @@
 use crate::linalg::field::Field;
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::Deref};
 
 use super::field::CRing;
 
+/// Side length of the tiles used by the blocked `compose` implementations
+pub(crate) const COMPOSE_BLOCK: usize = 64;
+
+/// Number of multiply-adds from which `compose` switches from the naive loop
+/// to the blocked parallel multiply, below this the threading overhead dominates
+pub(crate) const PARALLEL_COMPOSE_THRESHOLD: usize = 1 << 16;
+
 pub trait RModMorphism<R: CRing> {
     fn zero(domain: usize, codomain: usize) -> Self;
     fn identity(d: usize) -> Self;
@@ -11,7 +18,14 @@ pub trait RModMorphism<R: CRing> {
     fn set(&mut self, domain: usize, codomain: usize, f: R);
     fn add_at(&mut self, domain: usize, codomain: usize, f: R);
 
-    fn get_row(&self, codomain: usize) -> &[R];
+    /// Row as returned by `get_row`, a slice for dense backends and
+    /// an unpacked `Vec` for packed ones
+    type Row<'a>: Deref<Target = [R]>
+    where
+        Self: 'a,
+        R: 'a;
+
+    fn get_row(&self, codomain: usize) -> Self::Row<'_>;
     fn set_row(&mut self, codomain: usize, row: &[R]);
 
     fn is_row_non_zero(&self, codomain: usize) -> bool {
@@ -35,13 +49,117 @@ pub trait Matrix<F: Field>: RModMorphism<F> + Clone + Send + Sync + PartialEq +
     // nah, both
     fn pivots(&self) -> Vec<(usize, usize)>;
 
-    // Returns the change of basis matrix for the domain!
     fn rref(&mut self);
 
+    /// Row reduces self and returns `(T, T^-1)` where `T` is the change of basis of the codomain
+    /// that was applied, so the reduced matrix equals `T.compose(original)`.
+    fn rref_with_transform(&mut self) -> (Self, Self) {
+        // Row reduce [A | I], the right block then records all the row operations.
+        // Pivots only enter the right block in rows which are zero on the left.
+        let codomain = self.codomain();
+        let mut augmented = augment(self, &Self::identity(codomain));
+        augmented.rref();
+        let (reduced, transform) = split_columns(&augmented, self.domain());
+        *self = reduced;
+
+        // Row reducing [T | I] gives [I | T^-1] as T is invertible
+        let mut augmented = augment(&transform, &Self::identity(codomain));
+        augmented.rref();
+        let (_, inverse) = split_columns(&augmented, codomain);
+        (transform, inverse)
+    }
+
     fn kernel(&self) -> Self;
     fn cokernel(&self) -> Self {
         self.transpose().kernel()
     }
 
     fn first_non_zero_entry(&self) -> Option<(usize, usize)>;
+
+    fn rank(&self) -> usize {
+        let mut clone = self.clone();
+        clone.rref();
+        clone.pivots().len()
+    }
+
+    /// Basis of the image as rows, in reduced row echelon form.
+    /// So the domain of the result is the codomain of self.
+    fn image(&self) -> Self {
+        let mut trans = self.transpose();
+        trans.rref();
+        let rank = trans.pivots().len();
+        let mut image = Self::zero(self.codomain(), rank);
+        for i in 0..rank {
+            image.set_row(i, &trans.get_row(i));
+        }
+        image
+    }
+
+    /// Some x in the domain with self(x) = v, picking zero for all free variables
+    fn solve(&self, v: &[F]) -> Option<Vec<F>> {
+        debug_assert_eq!(v.len(), self.codomain(), "Vector is not in the codomain");
+        let mut column = Self::zero(1, self.codomain());
+        for (i, x) in v.iter().enumerate() {
+            column.set(0, i, *x);
+        }
+        let mut augmented = augment(self, &column);
+        augmented.rref();
+        solution_from_rref(&augmented)
+    }
+
+    fn is_in_image(&self, v: &[F]) -> bool {
+        self.solve(v).is_some()
+    }
+
+    /// Canonical representative of v in the quotient codomain / image,
+    /// which is zero precisely when v is in the image
+    fn reduce_modulo_image(&self, v: &[F]) -> Vec<F> {
+        let image = self.image();
+        let mut v = v.to_vec();
+        for (col, row) in image.pivots() {
+            let factor = v[col];
+            if !factor.is_zero() {
+                for (x, y) in v.iter_mut().zip(image.get_row(row).iter()) {
+                    *x -= factor * *y;
+                }
+            }
+        }
+        v
+    }
+}
+
+/// The matrix [l | r], both need the same codomain
+fn augment<F: Field, M: Matrix<F>>(l: &M, r: &M) -> M {
+    let mut augmented = M::zero(l.domain() + r.domain(), l.codomain());
+    for i in 0..l.codomain() {
+        let mut row = l.get_row(i).to_vec();
+        row.extend_from_slice(&r.get_row(i));
+        augmented.set_row(i, &row);
+    }
+    augmented
+}
+
+/// Splits [l | r] into l and r, where l has the given domain
+fn split_columns<F: Field, M: Matrix<F>>(m: &M, domain: usize) -> (M, M) {
+    let mut l = M::zero(domain, m.codomain());
+    let mut r = M::zero(m.domain() - domain, m.codomain());
+    for i in 0..m.codomain() {
+        let row = m.get_row(i);
+        l.set_row(i, &row[..domain]);
+        r.set_row(i, &row[domain..]);
+    }
+    (l, r)
+}
+
+/// Reads off a solution from a row reduced [A | v], None if v is not in the image of A
+pub(crate) fn solution_from_rref<F: Field, M: Matrix<F>>(augmented: &M) -> Option<Vec<F>> {
+    let last = augmented.domain() - 1;
+    let mut solution = vec![F::zero(); last];
+    for (col, row) in augmented.pivots() {
+        if col == last {
+            return None;
+        }
+        solution[col] = augmented.get(last, row);
+    }
+    Some(solution)
 }
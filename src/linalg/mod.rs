@@ -1,9 +1,19 @@
+pub mod chain_complex;
+pub mod echelon;
+pub mod extension_field;
+pub mod f2_matrix;
 pub mod field;
 pub mod flat_matrix;
+pub mod fp_matrix;
 pub mod graded;
 pub mod grading;
 pub mod matrix;
+pub mod presentation;
+pub mod rational;
+pub mod ring;
+pub mod ring_matrix;
 pub mod row_matrix;
+pub mod sparse_matrix;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    field::CRing,
+    grading::Grading,
+    matrix::RModMorphism,
+    ring::PrincipalIdealRing,
+    ring_matrix::{RingCokernel, RingMatrix},
+};
+
+/// Finitely presented module `R^generators / im(relations)`.
+///
+/// Every column of `relations` is a relation written in the generators.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Presentation<R: CRing> {
+    pub relations: RingMatrix<R>,
+}
+
+/// Map of finitely presented modules, given by the images of the generators of the domain.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ModuleMap<R: CRing> {
+    pub domain: Presentation<R>,
+    pub codomain: Presentation<R>,
+    pub map: RingMatrix<R>,
+}
+
+/// Grade-wise maps of finitely presented modules, e.g. `F_p[t]`-modules in every bidegree.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradedModuleMap<G: Grading, R: CRing> {
+    pub maps: BTreeMap<G, ModuleMap<R>>,
+}
+
+/// The matrix `[l | r]`
+fn hstack<R: CRing>(l: &RingMatrix<R>, r: &RingMatrix<R>) -> RingMatrix<R> {
+    let mut stacked = l.transpose();
+    stacked.vstack(&mut r.transpose());
+    stacked.transpose()
+}
+
+/// The first `n` coordinates of every kernel generator of `m`, as columns
+fn kernel_columns<R: PrincipalIdealRing>(m: &RingMatrix<R>, n: usize) -> RingMatrix<R> {
+    let generators = m.kernel().generators;
+    let mut columns = RingMatrix::zero(generators.codomain, n);
+    for i in 0..generators.codomain {
+        for j in 0..n {
+            columns.set(i, j, generators.get(j, i));
+        }
+    }
+    columns
+}
+
+impl<R: PrincipalIdealRing> Presentation<R> {
+    pub fn new(relations: RingMatrix<R>) -> Self {
+        Self { relations }
+    }
+
+    pub fn free(generators: usize) -> Self {
+        Self::new(RingMatrix::zero(0, generators))
+    }
+
+    pub fn generators(&self) -> usize {
+        self.relations.codomain
+    }
+
+    /// Invariant factors and free rank of the module
+    pub fn decompose(&self) -> RingCokernel<R> {
+        self.relations.cokernel()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        let decomposition = self.decompose();
+        decomposition.invariant_factors.is_empty() && decomposition.free_rank == 0
+    }
+
+    /// Whether the element with coordinates `v` in the generators vanishes in the module
+    pub fn is_zero_element(&self, v: &[R]) -> bool {
+        debug_assert_eq!(v.len(), self.generators(), "Element has the wrong length");
+        let decomposition = self.decompose();
+        (0..decomposition.projection.codomain).all(|i| {
+            let x: R = v
+                .iter()
+                .enumerate()
+                .map(|(j, y)| decomposition.projection.get(j, i) * y.clone())
+                .sum();
+            match decomposition.invariant_factors.get(i) {
+                Some(d) => R::divide(x, d.clone()).is_some(),
+                None => x.is_zero(),
+            }
+        })
+    }
+}
+
+impl<R: PrincipalIdealRing> ModuleMap<R> {
+    /// Checks that `map` sends every relation of the domain to zero
+    pub fn new(
+        domain: Presentation<R>,
+        codomain: Presentation<R>,
+        map: RingMatrix<R>,
+    ) -> Result<Self, String> {
+        if map.domain != domain.generators() || map.codomain != codomain.generators() {
+            return Err(format!(
+                "Map of size {}x{} does not fit {} and {} generators",
+                map.codomain,
+                map.domain,
+                domain.generators(),
+                codomain.generators()
+            ));
+        }
+        let relations = map.compose(&domain.relations).transpose();
+        for r in 0..relations.codomain {
+            if !codomain.is_zero_element(relations.get_row(r)) {
+                return Err(format!("Relation {} is not sent to zero", r));
+            }
+        }
+        Ok(Self {
+            domain,
+            codomain,
+            map,
+        })
+    }
+
+    /// Inclusion of the kernel into the domain.
+    ///
+    /// The kernel is generated by the `x` with `map x` in the relations of the codomain,
+    /// its relations are the combinations of those generators which are relations of the domain.
+    pub fn kernel(&self) -> Self {
+        let inclusion = kernel_columns(
+            &hstack(&self.map, &self.codomain.relations),
+            self.domain.generators(),
+        );
+        let relations = kernel_columns(
+            &hstack(&inclusion, &self.domain.relations),
+            inclusion.domain,
+        );
+        Self {
+            domain: Presentation::new(relations),
+            codomain: self.domain.clone(),
+            map: inclusion,
+        }
+    }
+
+    /// Projection of the codomain onto the cokernel
+    pub fn cokernel(&self) -> Self {
+        let relations = hstack(&self.map, &self.codomain.relations);
+        Self {
+            domain: self.codomain.clone(),
+            codomain: Presentation::new(relations),
+            map: RingMatrix::identity(self.codomain.generators()),
+        }
+    }
+}
+
+impl<G: Grading, R: PrincipalIdealRing> GradedModuleMap<G, R> {
+    pub fn get_kernel(&self) -> Self {
+        let maps = self
+            .maps
+            .par_iter()
+            .map(|(k, v)| (*k, v.kernel()))
+            .collect();
+        Self { maps }
+    }
+
+    pub fn get_cokernel(&self) -> Self {
+        let maps = self
+            .maps
+            .par_iter()
+            .map(|(k, v)| (*k, v.cokernel()))
+            .collect();
+        Self { maps }
+    }
+}
+
+impl<G: Grading, R: CRing> From<BTreeMap<G, ModuleMap<R>>> for GradedModuleMap<G, R> {
+    fn from(maps: BTreeMap<G, ModuleMap<R>>) -> Self {
+        Self { maps }
+    }
+}
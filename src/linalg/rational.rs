@@ -0,0 +1,202 @@
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::field::{CRing, Field};
+
+/// Exact rational number `num / den`.
+///
+/// Always normalized: `den > 0` and `gcd(num, den) = 1`, so equality is structural.
+/// Arithmetic is checked and panics on `i128` overflow instead of silently wrapping.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Q {
+    num: i128,
+    den: i128,
+}
+
+/// Greatest common divisor of the absolute values, which may be `2^127`
+fn unsigned_gcd(a: i128, b: i128) -> u128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    i128::try_from(unsigned_gcd(a, b)).unwrap_or_else(|_| overflow())
+}
+
+fn overflow() -> ! {
+    panic!("Rational arithmetic overflowed i128")
+}
+
+impl Q {
+    /// Creates `num / den` in normal form, `den` must be non-zero
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "Rational with zero denominator");
+        // Work with absolute values, as `i128::MIN` has no positive counterpart
+        let g = unsigned_gcd(num, den);
+        let (n, d) = (num.unsigned_abs() / g, den.unsigned_abs() / g);
+        let num = if (num < 0) != (den < 0) {
+            0i128.checked_sub_unsigned(n)
+        } else {
+            i128::try_from(n).ok()
+        };
+        Self {
+            num: num.unwrap_or_else(|| overflow()),
+            den: i128::try_from(d).unwrap_or_else(|_| overflow()),
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.den
+    }
+}
+
+impl From<i64> for Q {
+    fn from(value: i64) -> Self {
+        Self {
+            num: value as i128,
+            den: 1,
+        }
+    }
+}
+
+impl Add for Q {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        // Multiply by the smallest common multiple of the denominators to postpone overflow
+        let g = gcd(self.den, rhs.den);
+        let (l, r) = (self.den / g, rhs.den / g);
+        let num = self
+            .num
+            .checked_mul(r)
+            .and_then(|a| rhs.num.checked_mul(l).and_then(|b| a.checked_add(b)))
+            .unwrap_or_else(|| overflow());
+        let den = self.den.checked_mul(r).unwrap_or_else(|| overflow());
+        Self::new(num, den)
+    }
+}
+
+impl Sub for Q {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl Neg for Q {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            num: self.num.checked_neg().unwrap_or_else(|| overflow()),
+            den: self.den,
+        }
+    }
+}
+
+impl Mul for Q {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // Cancel crosswise first, the result is then already normalized
+        let g1 = gcd(self.num, rhs.den).max(1);
+        let g2 = gcd(rhs.num, self.den).max(1);
+        let num = (self.num / g1)
+            .checked_mul(rhs.num / g2)
+            .unwrap_or_else(|| overflow());
+        let den = (self.den / g2)
+            .checked_mul(rhs.den / g1)
+            .unwrap_or_else(|| overflow());
+        Self::new(num, den)
+    }
+}
+
+impl AddAssign for Q {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Q {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Q {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl std::fmt::Debug for Q {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl std::iter::Sum for Q {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl CRing for Q {
+    fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    fn one() -> Self {
+        Self { num: 1, den: 1 }
+    }
+
+    fn zero() -> Self {
+        Self { num: 0, den: 1 }
+    }
+
+    /// Parses integers `a` and fractions `a/b`
+    fn parse(input: &str) -> Result<Self, String> {
+        let err = || format!("Field: {} could not be parsed", input);
+        let (num, den) = match input.trim().split_once('/') {
+            Some((num, den)) => (
+                num.trim().parse().map_err(|_| err())?,
+                den.trim().parse().map_err(|_| err())?,
+            ),
+            None => (input.trim().parse().map_err(|_| err())?, 1),
+        };
+        if den == 0 {
+            return Err(format!("Field: {} has a zero denominator", input));
+        }
+        Ok(Self::new(num, den))
+    }
+}
+
+impl Field for Q {
+    fn inv(self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(Self::new(self.den, self.num))
+        }
+    }
+
+    fn get_characteristic() -> usize {
+        0
+    }
+
+    /// Like `f64`, rounds towards zero and saturates at the bounds of `usize`
+    fn as_usize(self) -> usize {
+        (self.num / self.den).clamp(0, usize::MAX as i128) as usize
+    }
+}
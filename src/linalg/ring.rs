@@ -0,0 +1,459 @@
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::{
+    field::{CRing, Field},
+    rational::Q,
+};
+
+/// Commutative ring in which every ideal is principal, like `Z`, `Z/p^k` and `Z_(p)`.
+///
+/// This is what the Smith and Hermite normal forms of `RingMatrix` need.
+pub trait PrincipalIdealRing: CRing {
+    /// Returns `(g, s, t, u, v)` with `g = s a + t b` a generator of the ideal `(a, b)`,
+    /// `u a + v b = 0` and `s v - t u = 1`, so `[[s, t], [u, v]]` is invertible.
+    fn xgcd(a: Self, b: Self) -> (Self, Self, Self, Self, Self);
+
+    /// `Some(q)` with `q b = a` if `b` divides `a`
+    fn divide(a: Self, b: Self) -> Option<Self>;
+
+    /// Canonical representative of `a` modulo the ideal `(b)`
+    fn rem(a: Self, b: Self) -> Self;
+
+    fn is_unit(&self) -> bool;
+
+    /// Generator of the annihilator ideal `{x | x self = 0}`
+    fn annihilator(&self) -> Self;
+
+    /// Returns `(c, u)` with `u` a unit and `c = u self` the canonical generator of `(self)`
+    fn canonical_associate(&self) -> (Self, Self);
+
+    /// Size of a non-zero element, elements of smaller size make better pivots
+    fn norm(&self) -> usize;
+}
+
+impl CRing for i64 {
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn zero() -> Self {
+        0
+    }
+
+    fn parse(input: &str) -> Result<Self, String> {
+        input
+            .trim()
+            .parse()
+            .map_err(|_| format!("Ring: {} could not be parsed", input))
+    }
+}
+
+impl PrincipalIdealRing for i64 {
+    fn xgcd(a: Self, b: Self) -> (Self, Self, Self, Self, Self) {
+        if a == 0 && b == 0 {
+            return (0, 1, 0, 0, 1);
+        }
+        let (mut r0, mut r1) = (a, b);
+        let (mut s0, mut s1) = (1, 0);
+        let (mut t0, mut t1) = (0, 1);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (s0, s1) = (s1, s0 - q * s1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        (r0, s0, t0, -b / r0, a / r0)
+    }
+
+    fn divide(a: Self, b: Self) -> Option<Self> {
+        match b {
+            0 => (a == 0).then_some(0),
+            _ => (a % b == 0).then_some(a / b),
+        }
+    }
+
+    fn rem(a: Self, b: Self) -> Self {
+        match b {
+            0 => a,
+            _ => a.rem_euclid(b),
+        }
+    }
+
+    fn is_unit(&self) -> bool {
+        *self == 1 || *self == -1
+    }
+
+    fn annihilator(&self) -> Self {
+        (*self == 0) as i64
+    }
+
+    fn canonical_associate(&self) -> (Self, Self) {
+        match *self < 0 {
+            true => (-*self, -1),
+            false => (*self, 1),
+        }
+    }
+
+    fn norm(&self) -> usize {
+        self.unsigned_abs() as usize
+    }
+}
+
+/// The ring `Z/p^k`, stored as the representative in `0..p^k`
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Zpk<const P: u32, const K: u32>(pub u64);
+
+impl<const P: u32, const K: u32> Zpk<P, K> {
+    pub const MODULUS: u64 = (P as u64).pow(K);
+
+    /// p-adic valuation, `K` for zero
+    pub fn valuation(&self) -> u32 {
+        let mut x = self.0;
+        if x == 0 {
+            return K;
+        }
+        let mut v = 0;
+        while x.is_multiple_of(P as u64) {
+            x /= P as u64;
+            v += 1;
+        }
+        v
+    }
+
+    fn unit_inverse(self) -> Self {
+        // Extended Euclid against the modulus, only valid for units
+        let (mut r0, mut r1) = (Self::MODULUS as i128, self.0 as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        debug_assert_eq!(r0, 1, "Element is not a unit");
+        Zpk(t0.rem_euclid(Self::MODULUS as i128) as u64)
+    }
+
+    /// p^v
+    fn prime_power(v: u32) -> Self {
+        Zpk((P as u64).pow(v) % Self::MODULUS)
+    }
+}
+
+impl<const P: u32, const K: u32> Add for Zpk<P, K> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Zpk((self.0 + rhs.0) % Self::MODULUS)
+    }
+}
+
+impl<const P: u32, const K: u32> Sub for Zpk<P, K> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Zpk((Self::MODULUS + self.0 - rhs.0) % Self::MODULUS)
+    }
+}
+
+impl<const P: u32, const K: u32> Neg for Zpk<P, K> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Zpk((Self::MODULUS - self.0) % Self::MODULUS)
+    }
+}
+
+impl<const P: u32, const K: u32> Mul for Zpk<P, K> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Zpk((self.0 as u128 * rhs.0 as u128 % Self::MODULUS as u128) as u64)
+    }
+}
+
+impl<const P: u32, const K: u32> AddAssign for Zpk<P, K> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u32, const K: u32> SubAssign for Zpk<P, K> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u32, const K: u32> MulAssign for Zpk<P, K> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: u32, const K: u32> std::fmt::Debug for Zpk<P, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u32, const K: u32> std::iter::Sum for Zpk<P, K> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<const P: u32, const K: u32> CRing for Zpk<P, K> {
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn one() -> Self {
+        Zpk(1 % Self::MODULUS)
+    }
+
+    fn zero() -> Self {
+        Zpk(0)
+    }
+
+    fn parse(input: &str) -> Result<Self, String> {
+        let value: i64 = input
+            .trim()
+            .parse()
+            .map_err(|_| format!("Ring: {} could not be parsed", input))?;
+        Ok(Zpk(value.rem_euclid(Self::MODULUS as i64) as u64))
+    }
+}
+
+impl<const P: u32, const K: u32> PrincipalIdealRing for Zpk<P, K> {
+    fn xgcd(a: Self, b: Self) -> (Self, Self, Self, Self, Self) {
+        // A local ring, so the element of smaller valuation generates (a, b)
+        if a.valuation() <= b.valuation() {
+            let q = Self::divide(b, a).expect("a has the smaller valuation");
+            (a, Self::one(), Self::zero(), -q, Self::one())
+        } else {
+            let q = Self::divide(a, b).expect("b has the smaller valuation");
+            (b, Self::zero(), Self::one(), -Self::one(), q)
+        }
+    }
+
+    fn divide(a: Self, b: Self) -> Option<Self> {
+        let (va, vb) = (a.valuation(), b.valuation());
+        if a.is_zero() {
+            return Some(Self::zero());
+        }
+        if vb > va {
+            return None;
+        }
+        // a = p^va u_a and b = p^vb u_b, so a / b = p^(va - vb) u_a / u_b
+        let pow = |v| (P as u64).pow(v);
+        let ua = Zpk(a.0 / pow(va));
+        let ub = Zpk(b.0 / pow(vb));
+        Some(Self::prime_power(va - vb) * ua * ub.unit_inverse())
+    }
+
+    fn rem(a: Self, b: Self) -> Self {
+        Zpk(a.0 % (P as u64).pow(b.valuation()))
+    }
+
+    fn is_unit(&self) -> bool {
+        K > 0 && self.valuation() == 0
+    }
+
+    fn annihilator(&self) -> Self {
+        Self::prime_power(K - self.valuation())
+    }
+
+    fn canonical_associate(&self) -> (Self, Self) {
+        let v = self.valuation();
+        if v == K {
+            return (Self::zero(), Self::one());
+        }
+        let unit = Zpk(self.0 / (P as u64).pow(v));
+        (Self::prime_power(v), unit.unit_inverse())
+    }
+
+    fn norm(&self) -> usize {
+        self.valuation() as usize
+    }
+}
+
+/// The localization `Z_(p)`, rationals whose denominator is coprime to `p`
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZLocal<const P: u32>(Q);
+
+impl<const P: u32> ZLocal<P> {
+    pub fn new(num: i128, den: i128) -> Option<Self> {
+        let q = Q::new(num, den);
+        (q.denominator() % P as i128 != 0).then_some(ZLocal(q))
+    }
+
+    /// p-adic valuation of a non-zero element
+    pub fn valuation(&self) -> u32 {
+        let mut n = self.0.numerator();
+        let mut v = 0;
+        while n != 0 && n % P as i128 == 0 {
+            n /= P as i128;
+            v += 1;
+        }
+        v
+    }
+
+    fn prime_power(v: u32) -> Self {
+        ZLocal(Q::new((P as i128).pow(v), 1))
+    }
+}
+
+impl<const P: u32> Add for ZLocal<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        ZLocal(self.0 + rhs.0)
+    }
+}
+
+impl<const P: u32> Sub for ZLocal<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        ZLocal(self.0 - rhs.0)
+    }
+}
+
+impl<const P: u32> Neg for ZLocal<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        ZLocal(-self.0)
+    }
+}
+
+impl<const P: u32> Mul for ZLocal<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        ZLocal(self.0 * rhs.0)
+    }
+}
+
+impl<const P: u32> AddAssign for ZLocal<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u32> SubAssign for ZLocal<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u32> MulAssign for ZLocal<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: u32> std::fmt::Debug for ZLocal<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl<const P: u32> std::iter::Sum for ZLocal<P> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<const P: u32> CRing for ZLocal<P> {
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    fn one() -> Self {
+        ZLocal(Q::one())
+    }
+
+    fn zero() -> Self {
+        ZLocal(Q::zero())
+    }
+
+    /// Parses `a/b` where `p` may not divide `b`
+    fn parse(input: &str) -> Result<Self, String> {
+        let q = Q::parse(input).map_err(|_| format!("Ring: {} could not be parsed", input))?;
+        Self::new(q.numerator(), q.denominator())
+            .ok_or(format!("Ring: {} is not in Z_({})", input, P))
+    }
+}
+
+impl<const P: u32> PrincipalIdealRing for ZLocal<P> {
+    fn xgcd(a: Self, b: Self) -> (Self, Self, Self, Self, Self) {
+        // A local ring, so the element of smaller valuation generates (a, b)
+        if !a.is_zero() && (b.is_zero() || a.valuation() <= b.valuation()) {
+            let q = Self::divide(b, a).expect("a has the smaller valuation");
+            (a, Self::one(), Self::zero(), -q, Self::one())
+        } else if !b.is_zero() {
+            let q = Self::divide(a, b).expect("b has the smaller valuation");
+            (b, Self::zero(), Self::one(), -Self::one(), q)
+        } else {
+            (
+                Self::zero(),
+                Self::one(),
+                Self::zero(),
+                Self::zero(),
+                Self::one(),
+            )
+        }
+    }
+
+    fn divide(a: Self, b: Self) -> Option<Self> {
+        if b.is_zero() {
+            return a.is_zero().then_some(Self::zero());
+        }
+        let q = a.0 * b.0.inv().expect("b is non-zero");
+        Self::new(q.numerator(), q.denominator())
+    }
+
+    fn rem(a: Self, b: Self) -> Self {
+        if b.is_zero() {
+            return a;
+        }
+        // Z_(p) / p^v = Z / p^v, so take the representative in 0..p^v
+        let modulus = (P as i128).pow(b.valuation());
+        let (num, den) = (a.0.numerator(), a.0.denominator());
+        let (mut r0, mut r1) = (modulus, den.rem_euclid(modulus));
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        ZLocal(Q::new((num * t0).rem_euclid(modulus), 1))
+    }
+
+    fn is_unit(&self) -> bool {
+        !self.is_zero() && self.valuation() == 0
+    }
+
+    fn annihilator(&self) -> Self {
+        match self.is_zero() {
+            true => Self::one(),
+            false => Self::zero(),
+        }
+    }
+
+    fn canonical_associate(&self) -> (Self, Self) {
+        if self.is_zero() {
+            return (Self::zero(), Self::one());
+        }
+        let c = Self::prime_power(self.valuation());
+        (c, Self::divide(c, *self).expect("Same valuation"))
+    }
+
+    fn norm(&self) -> usize {
+        self.valuation() as usize
+    }
+}
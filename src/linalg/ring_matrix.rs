@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+
+use super::{field::CRing, matrix::RModMorphism, ring::PrincipalIdealRing};
+
+/// Dense matrix over a commutative ring, stored row-major like `FlatMatrix`.
+///
+/// Over a `PrincipalIdealRing` it has Smith and Hermite normal forms,
+/// from which kernels and cokernels are computed including their torsion.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RingMatrix<R: CRing> {
+    pub data: Vec<R>,
+    pub domain: usize,
+    pub codomain: usize,
+}
+
+/// Smith normal form `diagonal = left * m * right` with `left` and `right` invertible.
+///
+/// Every diagonal entry divides the next one and is the canonical generator of its ideal.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SmithForm<R: CRing> {
+    /// The `min(domain, codomain)` diagonal entries
+    pub diagonal: Vec<R>,
+    /// Base change of the codomain
+    pub left: RingMatrix<R>,
+    /// Base change of the domain
+    pub right: RingMatrix<R>,
+}
+
+/// Kernel of a map as the direct sum of `R / (annihilators[i])`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RingKernel<R: CRing> {
+    /// Every row is a generator, written in the basis of the domain
+    pub generators: RingMatrix<R>,
+    /// Generator of the annihilator of each generator, zero for free summands
+    pub annihilators: Vec<R>,
+}
+
+/// Cokernel of a map as `R / (d_1) + ... + R / (d_k) + R^free_rank`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RingCokernel<R: CRing> {
+    /// Non-zero non-unit invariant factors, each dividing the next
+    pub invariant_factors: Vec<R>,
+    pub free_rank: usize,
+    /// Map from the codomain onto the torsion generators followed by the free generators
+    pub projection: RingMatrix<R>,
+}
+
+impl<R: CRing> RingMatrix<R> {
+    fn get_element(&self, row: usize, col: usize) -> R {
+        self.data[row * self.domain + col].clone()
+    }
+
+    fn set_element(&mut self, row: usize, col: usize, value: R) {
+        self.data[row * self.domain + col] = value;
+    }
+
+    fn row_slice(&self, row: usize) -> &[R] {
+        let start = row * self.domain;
+        &self.data[start..start + self.domain]
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for j in 0..self.domain {
+            self.data.swap(a * self.domain + j, b * self.domain + j);
+        }
+    }
+
+    fn swap_columns(&mut self, a: usize, b: usize) {
+        for i in 0..self.codomain {
+            self.data.swap(i * self.domain + a, i * self.domain + b);
+        }
+    }
+
+    fn scale_row(&mut self, row: usize, factor: R) {
+        for j in 0..self.domain {
+            self.data[row * self.domain + j] *= factor.clone();
+        }
+    }
+
+    fn add_row_multiple(&mut self, source: usize, target: usize, factor: R) {
+        for j in 0..self.domain {
+            let s = self.get_element(source, j);
+            self.data[target * self.domain + j] += factor.clone() * s;
+        }
+    }
+
+    /// Replaces rows a and b by `s a + t b` and `u a + v b`
+    fn combine_rows(&mut self, a: usize, b: usize, (s, t, u, v): (R, R, R, R)) {
+        for j in 0..self.domain {
+            let (x, y) = (self.get_element(a, j), self.get_element(b, j));
+            self.set_element(a, j, s.clone() * x.clone() + t.clone() * y.clone());
+            self.set_element(b, j, u.clone() * x + v.clone() * y);
+        }
+    }
+
+    /// Replaces columns a and b by `s a + t b` and `u a + v b`
+    fn combine_columns(&mut self, a: usize, b: usize, (s, t, u, v): (R, R, R, R)) {
+        for i in 0..self.codomain {
+            let (x, y) = (self.get_element(i, a), self.get_element(i, b));
+            self.set_element(i, a, s.clone() * x.clone() + t.clone() * y.clone());
+            self.set_element(i, b, u.clone() * x + v.clone() * y);
+        }
+    }
+}
+
+impl<R: PrincipalIdealRing> RingMatrix<R> {
+    /// Row echelon form `h = u * self` with `u` invertible.
+    ///
+    /// Pivots are canonical and the entries above a pivot are reduced modulo it,
+    /// so over `Z` this is the usual Hermite normal form.
+    pub fn hermite_normal_form(&self) -> (Self, Self) {
+        let mut h = self.clone();
+        let mut u = Self::identity(self.codomain);
+
+        let mut r = 0;
+        for c in 0..self.domain {
+            if r == self.codomain {
+                break;
+            }
+
+            for i in r + 1..self.codomain {
+                let below = h.get_element(i, c);
+                if !below.is_zero() {
+                    let (_, s, t, x, y) = R::xgcd(h.get_element(r, c), below);
+                    let combination = (s, t, x, y);
+                    h.combine_rows(r, i, combination.clone());
+                    u.combine_rows(r, i, combination);
+                }
+            }
+
+            let pivot = h.get_element(r, c);
+            if pivot.is_zero() {
+                continue;
+            }
+            let (pivot, unit) = pivot.canonical_associate();
+            h.scale_row(r, unit.clone());
+            u.scale_row(r, unit);
+
+            for i in 0..r {
+                let above = h.get_element(i, c);
+                let q = R::divide(above.clone() - R::rem(above, pivot.clone()), pivot.clone())
+                    .expect("Remainder should differ by a multiple");
+                if !q.is_zero() {
+                    h.add_row_multiple(r, i, -q.clone());
+                    u.add_row_multiple(r, i, -q);
+                }
+            }
+            r += 1;
+        }
+
+        (h, u)
+    }
+
+    pub fn smith_normal_form(&self) -> SmithForm<R> {
+        let mut s = self.clone();
+        let mut left = Self::identity(self.codomain);
+        let mut right = Self::identity(self.domain);
+        let n = self.domain.min(self.codomain);
+
+        for t in 0..n {
+            // Move the non-zero entry of smallest norm to (t, t)
+            let pivot = (t..self.codomain)
+                .flat_map(|i| (t..self.domain).map(move |j| (i, j)))
+                .filter(|&(i, j)| !s.get_element(i, j).is_zero())
+                .min_by_key(|&(i, j)| s.get_element(i, j).norm());
+            let (i, j) = match pivot {
+                Some(p) => p,
+                None => break,
+            };
+            s.swap_rows(t, i);
+            left.swap_rows(t, i);
+            s.swap_columns(t, j);
+            right.swap_columns(t, j);
+
+            loop {
+                // Clearing the row can fill the column again, so repeat until both are clear
+                loop {
+                    for i in t + 1..self.codomain {
+                        let below = s.get_element(i, t);
+                        if !below.is_zero() {
+                            let (_, a, b, c, d) = R::xgcd(s.get_element(t, t), below);
+                            let combination = (a, b, c, d);
+                            s.combine_rows(t, i, combination.clone());
+                            left.combine_rows(t, i, combination);
+                        }
+                    }
+                    for j in t + 1..self.domain {
+                        let side = s.get_element(t, j);
+                        if !side.is_zero() {
+                            let (_, a, b, c, d) = R::xgcd(s.get_element(t, t), side);
+                            let combination = (a, b, c, d);
+                            s.combine_columns(t, j, combination.clone());
+                            right.combine_columns(t, j, combination);
+                        }
+                    }
+                    if (t + 1..self.codomain).all(|i| s.get_element(i, t).is_zero()) {
+                        break;
+                    }
+                }
+
+                // The pivot has to divide the rest of the matrix
+                let pivot = s.get_element(t, t);
+                let offender = (t + 1..self.codomain).find(|&i| {
+                    (t + 1..self.domain)
+                        .any(|j| R::divide(s.get_element(i, j), pivot.clone()).is_none())
+                });
+                match offender {
+                    Some(i) => {
+                        s.add_row_multiple(i, t, R::one());
+                        left.add_row_multiple(i, t, R::one());
+                    }
+                    None => break,
+                }
+            }
+
+            let (_, unit) = s.get_element(t, t).canonical_associate();
+            s.scale_row(t, unit.clone());
+            left.scale_row(t, unit);
+        }
+
+        SmithForm {
+            diagonal: (0..n).map(|i| s.get_element(i, i)).collect(),
+            left,
+            right,
+        }
+    }
+
+    pub fn rank(&self) -> usize {
+        self.smith_normal_form()
+            .diagonal
+            .iter()
+            .filter(|d| !d.is_zero())
+            .count()
+    }
+
+    /// Kernel generators from the Smith normal form.
+    ///
+    /// If `d x = 0` then `x` is a multiple of the annihilator of `d`,
+    /// so with zero divisors the kernel can contain torsion.
+    pub fn kernel(&self) -> RingKernel<R> {
+        let smith = self.smith_normal_form();
+        let mut rows = vec![];
+        let mut annihilators = vec![];
+        for i in 0..self.domain {
+            let d = smith.diagonal.get(i).cloned().unwrap_or(R::zero());
+            let a = d.annihilator();
+            if a.is_zero() {
+                continue;
+            }
+            let column: Vec<R> = (0..self.domain)
+                .map(|j| a.clone() * smith.right.get_element(j, i))
+                .collect();
+            rows.push(column);
+            annihilators.push(a.annihilator());
+        }
+
+        let mut generators = Self::zero(self.domain, rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            generators.set_row(i, row);
+        }
+        RingKernel {
+            generators,
+            annihilators,
+        }
+    }
+
+    pub fn cokernel(&self) -> RingCokernel<R> {
+        let smith = self.smith_normal_form();
+        let mut invariant_factors = vec![];
+        let mut free_rank = 0;
+        let mut rows = vec![];
+        for i in 0..self.codomain {
+            let d = smith.diagonal.get(i).cloned().unwrap_or(R::zero());
+            if d.is_unit() {
+                continue;
+            }
+            if d.is_zero() {
+                free_rank += 1;
+            } else {
+                invariant_factors.push(d);
+            }
+            rows.push(smith.left.get_row(i));
+        }
+
+        let mut projection = Self::zero(self.codomain, rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            projection.set_row(i, row);
+        }
+        RingCokernel {
+            invariant_factors,
+            free_rank,
+            projection,
+        }
+    }
+}
+
+impl<R: CRing> RModMorphism<R> for RingMatrix<R> {
+    fn zero(domain: usize, codomain: usize) -> Self {
+        Self {
+            data: vec![R::zero(); domain * codomain],
+            domain,
+            codomain,
+        }
+    }
+
+    fn identity(d: usize) -> Self {
+        let mut identity = Self::zero(d, d);
+        for i in 0..d {
+            identity.set_element(i, i, R::one());
+        }
+        identity
+    }
+
+    fn get(&self, domain: usize, codomain: usize) -> R {
+        self.get_element(codomain, domain)
+    }
+
+    fn set(&mut self, domain: usize, codomain: usize, r: R) {
+        self.set_element(codomain, domain, r);
+    }
+
+    fn add_at(&mut self, domain: usize, codomain: usize, r: R) {
+        self.data[codomain * self.domain + domain] += r;
+    }
+
+    type Row<'a>
+        = &'a [R]
+    where
+        R: 'a;
+
+    fn get_row(&self, codomain: usize) -> &[R] {
+        self.row_slice(codomain)
+    }
+
+    fn set_row(&mut self, codomain: usize, row: &[R]) {
+        let start = codomain * self.domain;
+        self.data[start..start + self.domain].clone_from_slice(row);
+    }
+
+    fn compose(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(
+            self.domain, rhs.codomain,
+            "Matrix domain not equal to codomain"
+        );
+
+        let mut compose = Self::zero(rhs.domain, self.codomain);
+        for x in 0..self.codomain {
+            for k in 0..self.domain {
+                let l = self.get_element(x, k);
+                if l.is_zero() {
+                    continue;
+                }
+                for y in 0..rhs.domain {
+                    compose.data[x * rhs.domain + y] += l.clone() * rhs.get_element(k, y);
+                }
+            }
+        }
+        compose
+    }
+
+    fn transpose(&self) -> Self {
+        let mut transpose = Self::zero(self.codomain, self.domain);
+        for i in 0..self.codomain {
+            for j in 0..self.domain {
+                transpose.set_element(j, i, self.get_element(i, j));
+            }
+        }
+        transpose
+    }
+
+    fn domain(&self) -> usize {
+        self.domain
+    }
+
+    fn codomain(&self) -> usize {
+        self.codomain
+    }
+
+    fn vstack(&mut self, other: &mut Self) {
+        debug_assert_eq!(
+            self.domain, other.domain,
+            "Domains of the two matrices do not have the same dimension"
+        );
+
+        self.data.extend_from_slice(&other.data);
+        self.codomain += other.codomain;
+    }
+
+    fn block_sum(&mut self, other: &Self) {
+        let mut new = Self::zero(self.domain + other.domain, self.codomain + other.codomain);
+        for i in 0..self.codomain {
+            for j in 0..self.domain {
+                new.set_element(i, j, self.get_element(i, j));
+            }
+        }
+        for i in 0..other.codomain {
+            for j in 0..other.domain {
+                new.set_element(self.codomain + i, self.domain + j, other.get_element(i, j));
+            }
+        }
+        *self = new;
+    }
+}
@@ -1,9 +1,10 @@
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::vec;
 
 use super::{
     field::Field,
-    matrix::{Matrix, RModMorphism},
+    matrix::{solution_from_rref, Matrix, RModMorphism, COMPOSE_BLOCK, PARALLEL_COMPOSE_THRESHOLD},
 };
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -14,49 +15,9 @@ pub struct RowMatrix<F: Field> {
 }
 
 impl<F: Field> RowMatrix<F> {
-    fn rref_kernel(&self) -> Self {
-        // Store pivot columns
-        let mut free_vars = Vec::new();
-
-        let pivot_cols: Vec<usize> = self.pivots().iter().map(|x| x.0).collect();
-
-        for j in 0..self.domain {
-            if !pivot_cols.contains(&j) {
-                free_vars.push(j);
-            }
-        }
-
-        // Initialize kernel matrix (one column per free variable)
-        let mut kernel = vec![vec![F::zero(); self.domain]; free_vars.len()];
-
-        for (i, &free_var) in free_vars.iter().enumerate() {
-            // Set the free variable coefficient to 1
-            kernel[i][free_var] = F::one();
-
-            // Back-substitute to find the pivot column contributions
-            for (row, &pivot_col) in pivot_cols.iter().enumerate() {
-                kernel[i][pivot_col] = -self.data[row][free_var];
-            }
-        }
-        Self {
-            data: kernel,
-            domain: self.domain,
-            codomain: free_vars.len(),
-        }
-    }
-}
-
-impl<F: Field> Matrix<F> for RowMatrix<F> {
-    fn kernel(&self) -> Self {
-        let mut clone = self.clone();
-        clone.rref();
-        let mut kernel = clone.rref_kernel();
-        // let mut transformed_kernel = kernel.compose(&mut changeofbasis);
-        kernel.rref();
-        kernel
-    }
-
-    fn rref(&mut self) {
+    /// Row reduces self, applying every row operation to `transform`
+    /// and its inverse as a column operation to `inverse`.
+    fn rref_tracked(&mut self, mut track: Option<(&mut Self, &mut Self)>) {
         let rows = self.codomain;
         let cols = self.domain;
 
@@ -81,6 +42,10 @@ impl<F: Field> Matrix<F> for RowMatrix<F> {
 
             // Swap rows to move the pivot row to the current row
             self.data.swap(i, r);
+            if let Some((t, t_inv)) = track.as_mut() {
+                t.data.swap(i, r);
+                t_inv.data.iter_mut().for_each(|row| row.swap(i, r));
+            }
 
             // Normalize the pivot row (make the leading coefficient 1)
             let pivot = self.data[r][lead];
@@ -89,6 +54,10 @@ impl<F: Field> Matrix<F> for RowMatrix<F> {
                 for j in 0..cols {
                     self.data[r][j] *= pivot_inv;
                 }
+                if let Some((t, t_inv)) = track.as_mut() {
+                    t.data[r].iter_mut().for_each(|x| *x *= pivot_inv);
+                    t_inv.data.iter_mut().for_each(|row| row[r] *= pivot);
+                }
             }
 
             // Eliminate all other entries in the leading column
@@ -99,6 +68,19 @@ impl<F: Field> Matrix<F> for RowMatrix<F> {
                         let temp = factor * self.data[r][j];
                         self.data[i][j] -= temp;
                     }
+                    if let Some((t, t_inv)) = track.as_mut() {
+                        if !factor.is_zero() {
+                            // row_i -= factor * row_r is undone by col_r += factor * col_i
+                            for j in 0..t.domain {
+                                let temp = factor * t.data[r][j];
+                                t.data[i][j] -= temp;
+                            }
+                            t_inv.data.iter_mut().for_each(|row| {
+                                let temp = factor * row[i];
+                                row[r] += temp;
+                            });
+                        }
+                    }
                 }
             }
 
@@ -106,6 +88,115 @@ impl<F: Field> Matrix<F> for RowMatrix<F> {
         }
     }
 
+    /// Multiplies tile by tile, every block of output rows in parallel
+    fn compose_blocked(&self, rhs: &Self) -> Self {
+        let width = rhs.domain;
+        let mut compose = vec![vec![F::zero(); width]; self.codomain];
+
+        compose
+            .par_chunks_mut(COMPOSE_BLOCK)
+            .zip(self.data.par_chunks(COMPOSE_BLOCK))
+            .for_each(|(out, lhs)| {
+                for k_start in (0..self.domain).step_by(COMPOSE_BLOCK) {
+                    let k_end = (k_start + COMPOSE_BLOCK).min(self.domain);
+                    for j_start in (0..width).step_by(COMPOSE_BLOCK) {
+                        let j_end = (j_start + COMPOSE_BLOCK).min(width);
+                        for (out_row, lhs_row) in out.iter_mut().zip(lhs) {
+                            let lhs_block = &lhs_row[k_start..k_end];
+                            for (a, rhs_row) in lhs_block.iter().zip(&rhs.data[k_start..k_end]) {
+                                if a.is_zero() {
+                                    continue;
+                                }
+                                let rhs_row = &rhs_row[j_start..j_end];
+                                for (o, b) in out_row[j_start..j_end].iter_mut().zip(rhs_row) {
+                                    *o += *a * *b;
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+
+        Self {
+            data: compose,
+            domain: width,
+            codomain: self.codomain,
+        }
+    }
+
+    fn rref_kernel(&self) -> Self {
+        // Store pivot columns
+        let mut free_vars = Vec::new();
+
+        let pivot_cols: Vec<usize> = self.pivots().iter().map(|x| x.0).collect();
+
+        for j in 0..self.domain {
+            if !pivot_cols.contains(&j) {
+                free_vars.push(j);
+            }
+        }
+
+        // Initialize kernel matrix (one column per free variable)
+        let mut kernel = vec![vec![F::zero(); self.domain]; free_vars.len()];
+
+        for (i, &free_var) in free_vars.iter().enumerate() {
+            // Set the free variable coefficient to 1
+            kernel[i][free_var] = F::one();
+
+            // Back-substitute to find the pivot column contributions
+            for (row, &pivot_col) in pivot_cols.iter().enumerate() {
+                kernel[i][pivot_col] = -self.data[row][free_var];
+            }
+        }
+        Self {
+            data: kernel,
+            domain: self.domain,
+            codomain: free_vars.len(),
+        }
+    }
+}
+
+impl<F: Field> Matrix<F> for RowMatrix<F> {
+    fn kernel(&self) -> Self {
+        let mut clone = self.clone();
+        clone.rref();
+        let mut kernel = clone.rref_kernel();
+        // let mut transformed_kernel = kernel.compose(&mut changeofbasis);
+        kernel.rref();
+        kernel
+    }
+
+    fn rref(&mut self) {
+        self.rref_tracked(None);
+    }
+
+    fn rref_with_transform(&mut self) -> (Self, Self) {
+        let mut transform = Self::identity(self.codomain);
+        let mut inverse = Self::identity(self.codomain);
+        self.rref_tracked(Some((&mut transform, &mut inverse)));
+        (transform, inverse)
+    }
+
+    fn solve(&self, v: &[F]) -> Option<Vec<F>> {
+        debug_assert_eq!(v.len(), self.codomain, "Vector is not in the codomain");
+        let mut augmented = Self {
+            data: self
+                .data
+                .iter()
+                .zip(v)
+                .map(|(row, x)| {
+                    let mut row = row.clone();
+                    row.push(*x);
+                    row
+                })
+                .collect(),
+            domain: self.domain + 1,
+            codomain: self.codomain,
+        };
+        augmented.rref();
+        solution_from_rref(&augmented)
+    }
+
     /// (Column, Row) == (domain, codomain)
     fn pivots(&self) -> Vec<(usize, usize)> {
         let mut col = 0;
@@ -214,6 +305,11 @@ impl<F: Field> RModMorphism<F> for RowMatrix<F> {
         self.data[codomain][domain] += f;
     }
 
+    type Row<'a>
+        = &'a [F]
+    where
+        F: 'a;
+
     fn get_row(&self, codomain: usize) -> &[F] {
         self.data[codomain].as_slice()
     }
@@ -236,6 +332,10 @@ impl<F: Field> RModMorphism<F> for RowMatrix<F> {
             "Matrix domain not equal to codomain"
         );
 
+        if self.codomain * self.domain * rhs.domain >= PARALLEL_COMPOSE_THRESHOLD {
+            return self.compose_blocked(rhs);
+        }
+
         let trans = rhs.transpose();
         let mut compose = vec![vec![F::zero(); trans.codomain]; self.codomain];
 
@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    field::Field,
+    flat_matrix::FlatMatrix,
+    matrix::{Matrix, RModMorphism},
+};
+
+/// Row-sparse matrix, every row only stores its non-zero entries.
+///
+/// A row is a list of `(domain index, value)` sorted by domain index without any zero values,
+/// so two matrices are equal precisely when their entries are.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SparseMatrix<F: Field> {
+    pub data: Vec<Vec<(usize, F)>>,
+    pub domain: usize,
+    pub codomain: usize,
+}
+
+/// target += factor * source, on sorted sparse rows
+fn add_scaled_row<F: Field>(
+    target: &[(usize, F)],
+    source: &[(usize, F)],
+    factor: F,
+) -> Vec<(usize, F)> {
+    let mut result = Vec::with_capacity(target.len() + source.len());
+    let (mut t, mut s) = (0, 0);
+    while t < target.len() || s < source.len() {
+        if s == source.len() || (t < target.len() && target[t].0 < source[s].0) {
+            result.push(target[t]);
+            t += 1;
+        } else if t == target.len() || source[s].0 < target[t].0 {
+            result.push((source[s].0, factor * source[s].1));
+            s += 1;
+        } else {
+            let val = target[t].1 + factor * source[s].1;
+            if !val.is_zero() {
+                result.push((target[t].0, val));
+            }
+            t += 1;
+            s += 1;
+        }
+    }
+    result
+}
+
+impl<F: Field> SparseMatrix<F> {
+    fn find(&self, domain: usize, codomain: usize) -> Result<usize, usize> {
+        self.data[codomain].binary_search_by_key(&domain, |(j, _)| *j)
+    }
+
+    fn rref_kernel(&self) -> Self {
+        let pivots = self.pivots();
+        let mut free_index = vec![None; self.domain];
+        let mut free_vars = 0;
+        let mut is_pivot = vec![false; self.domain];
+        for &(col, _) in &pivots {
+            is_pivot[col] = true;
+        }
+        for (index, &pivot) in free_index.iter_mut().zip(&is_pivot) {
+            if !pivot {
+                *index = Some(free_vars);
+                free_vars += 1;
+            }
+        }
+
+        let mut kernel: Vec<Vec<(usize, F)>> = vec![vec![]; free_vars];
+        for (j, index) in free_index.iter().enumerate() {
+            if let Some(i) = index {
+                kernel[*i].push((j, F::one()));
+            }
+        }
+
+        for &(pivot_col, row) in &pivots {
+            for &(j, val) in &self.data[row] {
+                if let Some(i) = free_index[j] {
+                    kernel[i].push((pivot_col, -val));
+                }
+            }
+        }
+        kernel
+            .iter_mut()
+            .for_each(|row| row.sort_by_key(|(j, _)| *j));
+
+        Self {
+            data: kernel,
+            domain: self.domain,
+            codomain: free_vars,
+        }
+    }
+
+    /// Number of stored (non-zero) entries
+    pub fn non_zero_entries(&self) -> usize {
+        self.data.iter().map(|row| row.len()).sum()
+    }
+}
+
+impl<F: Field> Matrix<F> for SparseMatrix<F> {
+    fn kernel(&self) -> Self {
+        let mut clone = self.clone();
+        clone.rref();
+        let mut kernel = clone.rref_kernel();
+        kernel.rref();
+        kernel
+    }
+
+    fn rref(&mut self) {
+        for r in 0..self.codomain {
+            // All rows from r onwards are zero left of the previous pivot,
+            // so the next pivot is the leftmost entry among them
+            let pivot = (r..self.codomain)
+                .filter_map(|i| self.data[i].first().map(|(j, _)| (*j, i)))
+                .min();
+            let (lead, pivot_row) = match pivot {
+                Some(p) => p,
+                None => return,
+            };
+
+            self.data.swap(r, pivot_row);
+
+            let pivot_inv = self.data[r][0].1.inv().expect("Pivot should be invertible");
+            self.data[r].iter_mut().for_each(|(_, x)| *x *= pivot_inv);
+
+            let pivot = std::mem::take(&mut self.data[r]);
+            for i in 0..self.codomain {
+                if i == r {
+                    continue;
+                }
+                if let Ok(idx) = self.find(lead, i) {
+                    let factor = -self.data[i][idx].1;
+                    self.data[i] = add_scaled_row(&self.data[i], &pivot, factor);
+                }
+            }
+            self.data[r] = pivot;
+        }
+    }
+
+    fn pivots(&self) -> Vec<(usize, usize)> {
+        let mut col = 0;
+        let mut pivots = vec![];
+        for row in 0..self.codomain {
+            let idx = self.data[row].partition_point(|(j, _)| *j < col);
+            match self.data[row].get(idx) {
+                Some((j, _)) => {
+                    pivots.push((*j, row));
+                    col = j + 1;
+                }
+                None => col = self.domain,
+            }
+        }
+        pivots
+    }
+
+    fn first_non_zero_entry(&self) -> Option<(usize, usize)> {
+        self.data
+            .iter()
+            .enumerate()
+            .find_map(|(codom_id, row)| row.first().map(|(dom_id, _)| (codom_id, *dom_id)))
+    }
+}
+
+impl<F: Field> RModMorphism<F> for SparseMatrix<F> {
+    fn zero(domain: usize, codomain: usize) -> Self {
+        Self {
+            data: vec![vec![]; codomain],
+            domain,
+            codomain,
+        }
+    }
+
+    fn identity(d: usize) -> Self {
+        Self {
+            data: (0..d).map(|i| vec![(i, F::one())]).collect(),
+            domain: d,
+            codomain: d,
+        }
+    }
+
+    fn get(&self, domain: usize, codomain: usize) -> F {
+        match self.find(domain, codomain) {
+            Ok(idx) => self.data[codomain][idx].1,
+            Err(_) => F::zero(),
+        }
+    }
+
+    fn set(&mut self, domain: usize, codomain: usize, f: F) {
+        match (self.find(domain, codomain), f.is_zero()) {
+            (Ok(idx), true) => {
+                self.data[codomain].remove(idx);
+            }
+            (Ok(idx), false) => self.data[codomain][idx].1 = f,
+            (Err(_), true) => {}
+            (Err(idx), false) => self.data[codomain].insert(idx, (domain, f)),
+        }
+    }
+
+    fn add_at(&mut self, domain: usize, codomain: usize, f: F) {
+        let val = self.get(domain, codomain) + f;
+        self.set(domain, codomain, val);
+    }
+
+    type Row<'a>
+        = Vec<F>
+    where
+        F: 'a;
+
+    fn get_row(&self, codomain: usize) -> Vec<F> {
+        let mut row = vec![F::zero(); self.domain];
+        for &(j, val) in &self.data[codomain] {
+            row[j] = val;
+        }
+        row
+    }
+
+    fn set_row(&mut self, codomain: usize, row: &[F]) {
+        self.data[codomain] = row
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| !x.is_zero())
+            .map(|(j, x)| (j, *x))
+            .collect();
+    }
+
+    fn is_row_non_zero(&self, codomain: usize) -> bool {
+        !self.data[codomain].is_empty()
+    }
+
+    // domain l == codomain r, l \circ r
+    fn compose(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(
+            self.domain, rhs.codomain,
+            "Matrix domain not equal to codomain"
+        );
+
+        let mut accumulator = vec![F::zero(); rhs.domain];
+        let mut touched = vec![false; rhs.domain];
+        let mut indices = vec![];
+
+        let data = self
+            .data
+            .iter()
+            .map(|row| {
+                for &(k, l_val) in row {
+                    for &(j, r_val) in &rhs.data[k] {
+                        if !touched[j] {
+                            touched[j] = true;
+                            indices.push(j);
+                        }
+                        accumulator[j] += l_val * r_val;
+                    }
+                }
+                indices.sort_unstable();
+                let out = indices
+                    .drain(..)
+                    .filter_map(|j| {
+                        touched[j] = false;
+                        let val = std::mem::replace(&mut accumulator[j], F::zero());
+                        (!val.is_zero()).then_some((j, val))
+                    })
+                    .collect();
+                out
+            })
+            .collect();
+
+        Self {
+            data,
+            domain: rhs.domain,
+            codomain: self.codomain,
+        }
+    }
+
+    fn transpose(&self) -> Self {
+        let mut data = vec![vec![]; self.domain];
+        for (i, row) in self.data.iter().enumerate() {
+            for &(j, val) in row {
+                data[j].push((i, val));
+            }
+        }
+        Self {
+            data,
+            domain: self.codomain,
+            codomain: self.domain,
+        }
+    }
+
+    fn domain(&self) -> usize {
+        self.domain
+    }
+
+    fn codomain(&self) -> usize {
+        self.codomain
+    }
+
+    fn vstack(&mut self, other: &mut Self) {
+        debug_assert_eq!(
+            self.domain(),
+            other.domain(),
+            "Domains of the two matrices do not have the same dimension"
+        );
+
+        self.data.append(&mut other.data);
+        self.codomain += other.codomain;
+    }
+
+    fn block_sum(&mut self, other: &Self) {
+        let shift = self.domain;
+        self.data.extend(
+            other
+                .data
+                .iter()
+                .map(|row| row.iter().map(|&(j, val)| (j + shift, val)).collect()),
+        );
+        self.domain += other.domain;
+        self.codomain += other.codomain;
+    }
+}
+
+impl<F: Field> From<&FlatMatrix<F>> for SparseMatrix<F> {
+    fn from(value: &FlatMatrix<F>) -> Self {
+        let mut m = Self::zero(value.domain, value.codomain);
+        for i in 0..value.codomain {
+            m.set_row(i, value.get_row(i));
+        }
+        m
+    }
+}
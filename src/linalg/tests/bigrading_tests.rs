@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::linalg::grading::{BiGrading, Grading};
+    use crate::linalg::grading::{BiGrading, Grading, LimitPolicy};
 
     #[test]
     fn test_add() {
@@ -75,8 +75,26 @@ mod tests {
             ("t-s".to_string(), "s".to_string())
         );
         assert_eq!(a.export_grade(), vec![1, 2]);
-        assert_eq!(a.incr(), BiGrading(2, 3));
         assert_eq!(BiGrading::zero(), BiGrading(0, 0));
         assert_eq!(BiGrading::infty(), BiGrading(i32::MAX, i32::MAX));
     }
+
+    #[test]
+    fn test_within() {
+        assert!(BiGrading(5, 100) < BiGrading(6, 0));
+        assert!(!BiGrading(5, 100).within(BiGrading(6, 0)));
+        assert!(BiGrading(5, -1).within(BiGrading(6, 0)));
+        assert!(BiGrading(6, 0).within(BiGrading(6, 0)));
+    }
+
+    #[test]
+    fn test_limit_policy() {
+        let limit = BiGrading(4, 2);
+        assert_eq!(LimitPolicy::default().next(limit), BiGrading(5, 3));
+        assert_eq!(LimitPolicy::Fixed.next(limit), limit);
+        assert_eq!(
+            LimitPolicy::Step(BiGrading(2, 0)).next(limit),
+            BiGrading(6, 2)
+        );
+    }
 }
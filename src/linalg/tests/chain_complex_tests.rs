@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::linalg::{
+        chain_complex::GradedChainComplex,
+        field::{CRing, Field, Fp, F2},
+        graded::GradedLinearMap,
+        matrix::RModMorphism,
+        row_matrix::RowMatrix,
+    };
+
+    type F3 = Fp<3>;
+
+    fn map<F: Field>(
+        grade: i32,
+        domain: usize,
+        codomain: usize,
+        entries: &[(usize, usize, F)],
+    ) -> GradedLinearMap<i32, F, RowMatrix<F>> {
+        let mut m = RowMatrix::zero(domain, codomain);
+        for (i, j, v) in entries {
+            m.set(*i, *j, *v);
+        }
+        GradedLinearMap::from(BTreeMap::from([(grade, m)]))
+    }
+
+    /// `k -> k` in grade 0 by the identity, which is acyclic
+    fn acyclic() -> GradedChainComplex<i32, F3, RowMatrix<F3>> {
+        GradedChainComplex::new(vec![map(0, 1, 1, &[(0, 0, F3::one())])])
+    }
+
+    #[test]
+    fn test_new_and_verify() {
+        let complex = acyclic();
+        assert_eq!(complex.dimensions, vec![BTreeMap::from([(0, 1)]); 2]);
+        assert!(complex.verify().is_ok());
+
+        // k -> k -> k by identities does not square to zero
+        let one = map(0, 1, 1, &[(0, 0, F2::one())]);
+        let not_complex = GradedChainComplex::new(vec![one.clone(), one]);
+        assert!(not_complex.verify().is_err());
+
+        let mut wrong_size = acyclic();
+        wrong_size.dimensions[1].insert(0, 2);
+        assert!(wrong_size.verify().is_err());
+    }
+
+    #[test]
+    fn test_homology() {
+        // C_0 = k^2 in grade 0, C_1 = k in grades 0 and 1, d = (1, 1)
+        let mut complex: GradedChainComplex<i32, F3, RowMatrix<F3>> =
+            GradedChainComplex::new(vec![map(0, 2, 1, &[(0, 0, F3::one()), (1, 0, F3::one())])]);
+        complex.dimensions[1].insert(1, 1);
+        assert!(complex.verify().is_ok());
+
+        let h0 = complex.homology(0);
+        assert_eq!(h0.len(), 1);
+        let cycle = &h0[&0][0];
+        assert_eq!(cycle[0] + cycle[1], F3::zero());
+        assert!(!cycle[0].is_zero());
+
+        let h1 = complex.homology(1);
+        assert!(!h1.contains_key(&0));
+        assert_eq!(h1[&1], vec![vec![F3::one()]]);
+        assert!(complex.homology(2).is_empty());
+    }
+
+    #[test]
+    fn test_shifts() {
+        let complex = acyclic().shift_grading(5).shift(2);
+        assert_eq!(complex.len(), 4);
+        assert!(complex.verify().is_ok());
+        assert_eq!(complex.dimension(2, &5), 1);
+        assert_eq!(complex.dimension(2, &0), 0);
+
+        // A single space in degree 2
+        let single =
+            GradedChainComplex::<i32, F3, RowMatrix<F3>>::concentrated(BTreeMap::from([(1, 2)]))
+                .shift(2);
+        assert!(single.verify().is_ok());
+        assert_eq!(
+            single.betti_numbers(),
+            vec![BTreeMap::new(), BTreeMap::new(), BTreeMap::from([(1, 2)])]
+        );
+    }
+
+    #[test]
+    fn test_tensor() {
+        // Without the sign d d would be 2 on the middle term
+        let square = acyclic().tensor(&acyclic());
+        assert_eq!(square.len(), 3);
+        assert_eq!(square.dimension(1, &0), 2);
+        assert!(square.verify().is_ok());
+        assert!(square.betti_numbers().iter().all(|b| b.is_empty()));
+
+        // Künneth: H(C ⊗ D) = H(C) ⊗ H(D)
+        let c: GradedChainComplex<i32, F3, RowMatrix<F3>> =
+            GradedChainComplex::concentrated(BTreeMap::from([(0, 1), (2, 1)]));
+        let mut d =
+            GradedChainComplex::new(vec![map(0, 2, 1, &[(0, 0, F3::one()), (1, 0, F3::one())])]);
+        d.dimensions[1].insert(3, 1);
+        let product = c.tensor(&d).shift_grading(1);
+        assert!(product.verify().is_ok());
+        assert_eq!(
+            product.betti_numbers(),
+            vec![
+                BTreeMap::from([(1, 1), (3, 1)]),
+                BTreeMap::from([(4, 1), (6, 1)])
+            ]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::linalg::{
+        echelon::EchelonForm,
+        field::{Fp, F2},
+        flat_matrix::FlatMatrix,
+        matrix::{Matrix, RModMorphism},
+        tests::random_flat,
+    };
+
+    type TestField = Fp<5>;
+
+    // Mostly zero entries, so rows are often dependent
+    fn random_rows(width: usize, count: usize, seed: u64) -> Vec<Vec<TestField>> {
+        let m = random_flat::<TestField>(width, count, seed, 1);
+        (0..count).map(|i| m.get_row(i).to_vec()).collect()
+    }
+
+    fn to_flat(rows: &[Vec<TestField>], width: usize) -> FlatMatrix<TestField> {
+        let mut m = FlatMatrix::zero(width, rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            m.set_row(i, row);
+        }
+        m
+    }
+
+    #[test]
+    fn test_matches_flat_rref() {
+        for seed in 0..20 {
+            let width = (seed % 7) as usize + 1;
+            let rows = random_rows(width, 9, seed);
+            let mut echelon = EchelonForm::new(width);
+
+            for k in 0..rows.len() {
+                let flat = to_flat(&rows[..k], width);
+                assert_eq!(echelon.rank(), flat.rank());
+                assert_eq!(
+                    echelon.kernel_lead(),
+                    flat.kernel().first_non_zero_entry().map(|(_, col)| col)
+                );
+                assert_eq!(echelon.push_row(&rows[k]), flat.rank() < echelon.rank());
+            }
+
+            // Same span, so the rref'd basis agrees with FlatMatrix up to the order of the rows
+            let mut flat = to_flat(&rows, width);
+            flat.rref();
+            let mut sorted: Vec<(usize, Vec<TestField>)> = echelon
+                .pivots()
+                .iter()
+                .copied()
+                .zip(echelon.rows().iter().cloned())
+                .collect();
+            sorted.sort_by_key(|(p, _)| *p);
+            for (i, (_, row)) in sorted.iter().enumerate() {
+                assert_eq!(row, &flat.get_row(i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduce() {
+        let mut echelon = EchelonForm::new(3);
+        assert!(echelon.push_row(&[F2(1), F2(1), F2(0)]));
+        assert!(!echelon.push_row(&[F2(1), F2(1), F2(0)]));
+        assert!(echelon.push_row(&[F2(0), F2(1), F2(1)]));
+
+        assert!(echelon.is_pivot(0));
+        assert!(echelon.is_pivot(1));
+        assert!(!echelon.is_pivot(2));
+        assert_eq!(echelon.reduce(&[F2(1), F2(0), F2(1)]), vec![F2(0); 3]);
+        assert_eq!(
+            echelon.reduce(&[F2(0), F2(0), F2(1)]),
+            vec![F2(0), F2(0), F2(1)]
+        );
+
+        // Kernel is spanned by (1, 1, 1)
+        assert_eq!(echelon.kernel_lead(), Some(0));
+        assert!(echelon.push_row(&[F2(0), F2(0), F2(1)]));
+        assert_eq!(echelon.kernel_lead(), None);
+    }
+}
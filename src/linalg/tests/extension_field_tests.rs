@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use crate::galois_extension;
+    use crate::linalg::{
+        extension_field::{Fq, F16, F25, F27, F4, F49, F8, F9},
+        field::{CRing, Field},
+    };
+
+    galois_extension!(F9Alt, 3, [1, 0], "i");
+    type F9i = Fq<F9Alt>;
+
+    fn all_elements<E: crate::linalg::extension_field::GaloisExtension>() -> Vec<Fq<E>> {
+        let order = Fq::<E>::get_order();
+        (0..order)
+            .map(|mut x| {
+                let coeffs: Vec<u8> = (0..E::degree())
+                    .map(|_| {
+                        let c = (x % E::P as usize) as u8;
+                        x /= E::P as usize;
+                        c
+                    })
+                    .collect();
+                Fq::from_coefficients(&coeffs)
+            })
+            .collect()
+    }
+
+    fn check_field_axioms<E: crate::linalg::extension_field::GaloisExtension>() {
+        let elements = all_elements::<E>();
+        assert_eq!(elements.len(), Fq::<E>::get_order());
+        for (i, a) in elements.iter().enumerate() {
+            assert_eq!(a.as_usize(), i);
+            assert_eq!(*a + (-*a), Fq::zero());
+            match a.inv() {
+                Some(inv) => assert_eq!(*a * inv, Fq::one()),
+                None => assert!(a.is_zero()),
+            }
+        }
+        // The generator of a Conway polynomial generates the multiplicative group
+        let w = Fq::<E>::generator();
+        let order = Fq::<E>::get_order() as u64 - 1;
+        assert_eq!(w.pow(order), Fq::one());
+        for d in 1..order {
+            if order.is_multiple_of(d) {
+                assert_ne!(w.pow(d), Fq::one());
+            }
+        }
+    }
+
+    #[test]
+    fn test_conway_fields() {
+        check_field_axioms::<crate::linalg::extension_field::F4Ext>();
+        check_field_axioms::<crate::linalg::extension_field::F8Ext>();
+        check_field_axioms::<crate::linalg::extension_field::F16Ext>();
+        check_field_axioms::<crate::linalg::extension_field::F9Ext>();
+        check_field_axioms::<crate::linalg::extension_field::F27Ext>();
+        check_field_axioms::<crate::linalg::extension_field::F25Ext>();
+        check_field_axioms::<crate::linalg::extension_field::F49Ext>();
+    }
+
+    #[test]
+    fn test_f4_relation() {
+        let w = F4::generator();
+        assert_eq!(w * w + w + F4::one(), F4::zero());
+        assert_eq!(F4::parse("w^2").unwrap(), F4::parse("w+1").unwrap());
+        assert_eq!(w.inv().unwrap(), F4::parse("w^2").unwrap());
+        assert_eq!(format!("{:?}", w * w), "w+1");
+    }
+
+    #[test]
+    fn test_parse() {
+        let w = F9::generator();
+        assert_eq!(F9::parse("w").unwrap(), w);
+        assert_eq!(F9::parse("2w").unwrap(), w + w);
+        assert_eq!(F9::parse("2*w^3").unwrap(), (w + w) * w * w);
+        assert_eq!(F9::parse(" w^2 - 1 ").unwrap(), w * w - F9::one());
+        assert_eq!(F9::parse("-w").unwrap(), -w);
+        assert_eq!(F9::parse("4").unwrap(), F9::one());
+        assert_eq!(F9::parse("-1").unwrap(), F9::parse("2").unwrap());
+        assert_eq!(F9::parse("w^0").unwrap(), F9::one());
+
+        assert!(F9::parse("").is_err());
+        assert!(F9::parse("x").is_err());
+        assert!(F9::parse("w2").is_err());
+        assert!(F9::parse("w^").is_err());
+    }
+
+    #[test]
+    fn test_orders() {
+        assert_eq!(F4::get_characteristic(), 2);
+        assert_eq!(F4::get_order(), 4);
+        assert_eq!(F8::get_order(), 8);
+        assert_eq!(F16::get_order(), 16);
+        assert_eq!(F27::get_characteristic(), 3);
+        assert_eq!(F27::get_order(), 27);
+        assert_eq!(F25::get_order(), 25);
+        assert_eq!(F49::get_order(), 49);
+    }
+
+    #[test]
+    fn test_user_supplied_polynomial() {
+        // x^2 + 1 is irreducible over F3, but x is not a primitive element
+        check_field_axioms_without_primitivity();
+        let i = F9i::parse("i").unwrap();
+        assert_eq!(i * i, F9i::parse("-1").unwrap());
+        assert_eq!(i.pow(4), F9i::one());
+        assert_eq!(format!("{:?}", F9i::parse("2i+1").unwrap()), "2i+1");
+    }
+
+    fn check_field_axioms_without_primitivity() {
+        for a in all_elements::<F9Alt>() {
+            if !a.is_zero() {
+                assert_eq!(a * a.inv().unwrap(), F9i::one());
+            }
+        }
+    }
+
+    #[test]
+    fn test_reducible_modulus_has_no_inverse() {
+        // x^2 + 2 = (x + 1)(x + 2) over F3
+        galois_extension!(NotAField, 3, [2, 0], "x");
+        let x_plus_one = Fq::<NotAField>::parse("x+1").unwrap();
+        assert_eq!(x_plus_one.inv(), None);
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use crate::linalg::{
+        f2_matrix::F2Matrix,
+        field::{CRing, F2},
+        flat_matrix::FlatMatrix,
+        matrix::{Matrix, RModMorphism},
+        tests::{check_against_flat, random_flat},
+    };
+
+    fn assert_same(packed: &F2Matrix, flat: &FlatMatrix<F2>) {
+        assert_eq!(packed, &F2Matrix::from(flat));
+    }
+
+    const SHAPES: [(usize, usize); 8] = [
+        (0, 3),
+        (3, 0),
+        (1, 1),
+        (4, 3),
+        (63, 65),
+        (64, 64),
+        (130, 70),
+        (70, 130),
+    ];
+
+    #[test]
+    fn test_random_against_flat() {
+        for (seed, &(domain, codomain)) in SHAPES.iter().enumerate() {
+            check_against_flat::<F2, F2Matrix>(&random_flat(domain, codomain, seed as u64, 4));
+        }
+    }
+
+    #[test]
+    fn test_compose_transpose_match_flat() {
+        let l = random_flat::<F2>(70, 5, 7, 4);
+        let r = random_flat::<F2>(130, 70, 8, 4);
+        let (pl, pr) = (F2Matrix::from(&l), F2Matrix::from(&r));
+        assert_same(&pl.compose(&pr), &l.compose(&r));
+        assert_same(&pr.transpose(), &r.transpose());
+    }
+
+    #[test]
+    fn test_stacking_matches_flat() {
+        let mut a = random_flat::<F2>(65, 3, 1, 4);
+        let mut b = random_flat::<F2>(65, 2, 2, 4);
+        let c = random_flat::<F2>(7, 4, 3, 4);
+        let (mut pa, mut pb, pc) = (F2Matrix::from(&a), F2Matrix::from(&b), F2Matrix::from(&c));
+
+        a.vstack(&mut b);
+        pa.vstack(&mut pb);
+        assert_same(&pa, &a);
+
+        a.block_sum(&c);
+        pa.block_sum(&pc);
+        assert_same(&pa, &a);
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut m = F2Matrix::zero(100, 2);
+        m.set(70, 1, F2::one());
+        m.add_at(3, 0, F2::one());
+        assert_eq!(m.get(70, 1), F2::one());
+        assert_eq!(m.get(3, 0), F2::one());
+        assert_eq!(m.first_non_zero_entry(), Some((0, 3)));
+
+        m.set(70, 1, F2::zero());
+        assert!(!m.is_row_non_zero(1));
+        assert_eq!(m.get_row(0)[3], F2::one());
+    }
+
+    #[test]
+    fn test_identity() {
+        let id = F2Matrix::identity(66);
+        assert_same(&id, &FlatMatrix::identity(66));
+        assert_eq!(id.kernel(), F2Matrix::zero(66, 0));
+    }
+
+    #[test]
+    fn test_rref_with_transform() {
+        for (seed, &(domain, codomain)) in SHAPES.iter().enumerate() {
+            let flat = random_flat::<F2>(domain, codomain, seed as u64, 4);
+            let matrix = F2Matrix::from(&flat);
+
+            let mut reduced = matrix.clone();
+            let (t, t_inv) = reduced.rref_with_transform();
+
+            let mut expected = flat.clone();
+            expected.rref();
+            assert_same(&reduced, &expected);
+            assert_eq!(t.compose(&matrix), reduced);
+            assert_eq!(t_inv.compose(&reduced), matrix);
+            assert_eq!(t.compose(&t_inv), F2Matrix::identity(codomain));
+        }
+    }
+
+    #[test]
+    fn test_compose_four_russians() {
+        // Domains which are not a multiple of the table size
+        for (seed, &(n, k, m)) in [(100, 77, 150), (64, 8, 1), (65, 9, 130), (200, 64, 64)]
+            .iter()
+            .enumerate()
+        {
+            let l = random_flat::<F2>(k, n, seed as u64, 4);
+            let r = random_flat::<F2>(m, k, seed as u64 + 100, 4);
+            assert_same(
+                &F2Matrix::from(&l).compose(&F2Matrix::from(&r)),
+                &l.compose(&r),
+            );
+        }
+    }
+}
@@ -1,8 +1,10 @@
 #[cfg(test)]
 mod tests {
-    use crate::linalg::field::{CRing, Field, Fp};
+    use crate::linalg::field::{CRing, Field, Fp, Fp32};
 
     type Fp23 = Fp<23>;
+    type Fp65521 = Fp32<65521>;
+    type FpLarge = Fp32<4294967291>;
 
     #[test]
     fn test_fp23_addition() {
@@ -87,4 +89,62 @@ mod tests {
         let sum: Fp23 = values.into_iter().sum();
         assert_eq!(sum, Fp23 { 0: 2 }); // (3 + 5 + 17) mod 23 = 25 mod 23 = 2
     }
+
+    #[test]
+    fn test_fp32_arithmetic() {
+        let a = Fp65521 { 0: 65000 };
+        let b = Fp65521 { 0: 1000 };
+        assert_eq!(a + b, Fp65521 { 0: 479 });
+        assert_eq!(b - a, Fp65521 { 0: 1521 });
+        assert_eq!(-b, Fp65521 { 0: 64521 });
+        assert_eq!(-Fp65521::zero(), Fp65521::zero());
+        assert_eq!(
+            a * b,
+            Fp65521 {
+                0: (65000u64 * 1000 % 65521) as u32
+            }
+        );
+    }
+
+    #[test]
+    fn test_fp32_barrett_reduction() {
+        // Largest prime below 2^32, so products use the full 64 bits
+        let a = FpLarge { 0: 4294967290 };
+        assert_eq!(a * a, FpLarge::one());
+        let b = FpLarge { 0: 123456789 };
+        let c = FpLarge { 0: 3987654321 };
+        assert_eq!(
+            b * c,
+            FpLarge {
+                0: (123456789u64 * 3987654321 % 4294967291) as u32
+            }
+        );
+    }
+
+    #[test]
+    fn test_fp32_inversion() {
+        for x in [1, 2, 3, 1000, 65520] {
+            let a = Fp65521 { 0: x };
+            assert_eq!(a * a.inv().unwrap(), Fp65521::one());
+        }
+        let a = FpLarge { 0: 2 };
+        assert_eq!(a * a.inv().unwrap(), FpLarge::one());
+        assert_eq!(Fp65521::zero().inv(), None);
+    }
+
+    #[test]
+    fn test_fp32_parse() {
+        assert_eq!(Fp65521::parse("65522").unwrap(), Fp65521::one());
+        assert_eq!(Fp65521::parse("-1").unwrap(), Fp65521 { 0: 65520 });
+        assert!(Fp65521::parse("x").is_err());
+        assert_eq!(Fp65521::get_characteristic(), 65521);
+        assert_eq!(Fp65521::get_order(), 65521);
+    }
+
+    #[test]
+    fn test_fp32_sum() {
+        let values = vec![FpLarge { 0: 4294967290 }; 5];
+        let sum: FpLarge = values.into_iter().sum();
+        assert_eq!(sum, -FpLarge { 0: 5 });
+    }
 }
@@ -649,4 +649,139 @@ mod tests {
 
         assert_eq!(matrix.first_non_zero_entry(), Some((0, 0)));
     }
+
+    fn check_rref_with_transform<F: crate::linalg::field::Field>(matrix: FlatMatrix<F>) {
+        let mut reduced = matrix.clone();
+        let (t, t_inv) = reduced.rref_with_transform();
+
+        let mut expected = matrix.clone();
+        expected.rref();
+        assert_eq!(reduced, expected);
+        assert_eq!(t.compose(&matrix), reduced);
+        assert_eq!(t_inv.compose(&reduced), matrix);
+        assert_eq!(t.compose(&t_inv), FlatMatrix::identity(matrix.codomain));
+    }
+
+    #[test]
+    fn test_rref_with_transform() {
+        let mut matrix = FlatMatrix::<TestField>::zero(5, 6);
+        for i in 0..6 {
+            for j in 0..5 {
+                // Rows 1 and 4 agree, and row 3 is zero
+                let x = match i {
+                    3 => 0,
+                    4 => j * j + 1 + 2 * j,
+                    _ => j * j + i + 2 * i * j,
+                };
+                matrix.set(j, i, TestField { 0: (x % 23) as u8 });
+            }
+        }
+        check_rref_with_transform(matrix);
+        check_rref_with_transform(FlatMatrix::<TestField>::zero(3, 2));
+        check_rref_with_transform(FlatMatrix::<F2>::identity(4));
+        check_rref_with_transform(FlatMatrix::<F2>::zero(0, 2));
+    }
+
+    fn solve_matrix() -> FlatMatrix<TestField> {
+        // Maps (x, y, z) to (x + z, y + z, x + y + 2z), so the image is a plane
+        let mut matrix = FlatMatrix::zero(3, 3);
+        for (i, row) in [[1, 0, 1], [0, 1, 1], [1, 1, 2]].iter().enumerate() {
+            for (j, x) in row.iter().enumerate() {
+                matrix.set(j, i, TestField { 0: *x });
+            }
+        }
+        matrix
+    }
+
+    #[test]
+    fn test_solve() {
+        let matrix = solve_matrix();
+        let v = [TestField { 0: 3 }, TestField { 0: 5 }, TestField { 0: 8 }];
+
+        let x = matrix.solve(&v).unwrap();
+        assert_eq!(
+            x,
+            vec![TestField { 0: 3 }, TestField { 0: 5 }, TestField { 0: 0 }]
+        );
+        assert!(matrix.is_in_image(&v));
+
+        let w = [TestField { 0: 1 }, TestField { 0: 0 }, TestField { 0: 0 }];
+        assert_eq!(matrix.solve(&w), None);
+        assert!(!matrix.is_in_image(&w));
+
+        let empty = FlatMatrix::<TestField>::zero(0, 2);
+        assert_eq!(empty.solve(&[TestField::zero(); 2]), Some(vec![]));
+        assert_eq!(empty.solve(&[TestField::one(), TestField::zero()]), None);
+    }
+
+    #[test]
+    fn test_image_and_rank() {
+        let matrix = solve_matrix();
+        assert_eq!(matrix.rank(), 2);
+        assert_eq!(FlatMatrix::<TestField>::identity(4).rank(), 4);
+
+        let image = matrix.image();
+        assert_eq!(image.domain, 3);
+        assert_eq!(image.codomain, 2);
+        assert_eq!(
+            image.data,
+            vec![
+                TestField { 0: 1 },
+                TestField { 0: 0 },
+                TestField { 0: 1 },
+                TestField { 0: 0 },
+                TestField { 0: 1 },
+                TestField { 0: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reduce_modulo_image() {
+        let matrix = solve_matrix();
+        let v = [TestField { 0: 3 }, TestField { 0: 5 }, TestField { 0: 8 }];
+        assert_eq!(matrix.reduce_modulo_image(&v), vec![TestField::zero(); 3]);
+
+        // (1, 0, 0) and (0, 0, 22) differ by (1, 0, 1), which is in the image
+        let w = [TestField { 0: 1 }, TestField { 0: 0 }, TestField { 0: 0 }];
+        let u = [TestField { 0: 0 }, TestField { 0: 0 }, TestField { 0: 22 }];
+        assert_eq!(
+            matrix.reduce_modulo_image(&w),
+            matrix.reduce_modulo_image(&u)
+        );
+        assert_eq!(matrix.reduce_modulo_image(&u), u.to_vec());
+    }
+
+    #[test]
+    fn test_compose_large() {
+        // Big enough for the blocked parallel multiply, with sizes that are not multiples
+        // of the block size
+        let (n, k, m) = (70, 131, 90);
+        let entry = |i: usize, j: usize, seed: usize| TestField {
+            0: ((i * 7 + j * 13 + seed) * ((i + j) % 3) % 23) as u8,
+        };
+        let mut l = FlatMatrix::<TestField>::zero(k, n);
+        let mut r = FlatMatrix::<TestField>::zero(m, k);
+        for i in 0..n {
+            for j in 0..k {
+                l.set(j, i, entry(i, j, 1));
+            }
+        }
+        for i in 0..k {
+            for j in 0..m {
+                r.set(j, i, entry(i, j, 5));
+            }
+        }
+
+        let compose = l.compose(&r);
+        assert_eq!(compose.domain, m);
+        assert_eq!(compose.codomain, n);
+        for i in 0..n {
+            for j in 0..m {
+                let expected: TestField = (0..k).map(|x| l.get(x, i) * r.get(j, x)).sum();
+                assert_eq!(compose.get(j, i), expected);
+            }
+        }
+        assert_eq!(l.compose(&FlatMatrix::zero(0, k)), FlatMatrix::zero(0, n));
+    }
 }
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use crate::linalg::{
+        field::Fp,
+        flat_matrix::FlatMatrix,
+        fp_matrix::FpMatrix,
+        matrix::RModMorphism,
+        tests::{check_against_flat, random_flat},
+    };
+
+    fn flat<const P: u8>(data: &[u8], domain: usize, codomain: usize) -> FlatMatrix<Fp<P>> {
+        FlatMatrix {
+            data: data.iter().map(|&x| Fp(x)).collect(),
+            domain,
+            codomain,
+        }
+    }
+
+    fn check<const P: u8>(m: &FlatMatrix<Fp<P>>) {
+        check_against_flat::<Fp<P>, FpMatrix<P>>(m);
+    }
+
+    // The cases from flatmatrix_tests.rs
+    #[test]
+    fn test_flatmatrix_cases() {
+        check(&flat::<23>(&[2, 4, 8, 1, 2, 4], 3, 2));
+        check(&flat::<23>(&[0, 0, 8, 0, 2, 4], 3, 2));
+        check(&flat::<23>(&[1, 1, 0, 0, 1, 1], 3, 2));
+        check(&flat::<23>(&[1, 0], 1, 2));
+        check(&flat::<2>(&[0, 1, 0, 1, 1, 0, 1, 1], 4, 2));
+        check(&flat::<2>(&[1, 0, 1, 1, 0, 1, 0, 0, 0, 0, 1, 1], 4, 3));
+        check(&flat::<23>(&[1, 0, 3, 0, 1, 4], 3, 2));
+        check(&flat::<23>(&[0, 1, 0, 0, 0, 1], 3, 2));
+        check(&flat::<23>(&[0, 0, 0, 4], 2, 2));
+        check(&flat::<23>(&[5, 0, 0, 4], 2, 2));
+        check(&FlatMatrix::<Fp<23>>::identity(3));
+        check(&FlatMatrix::<Fp<23>>::zero(3, 0));
+    }
+
+    #[test]
+    fn test_random_against_flat() {
+        for (seed, &(domain, codomain)) in [(5, 7), (20, 20), (33, 12), (12, 40)].iter().enumerate()
+        {
+            check(&random_flat::<Fp<3>>(domain, codomain, seed as u64, 3));
+            check(&random_flat::<Fp<5>>(domain, codomain, seed as u64, 3));
+            check(&random_flat::<Fp<23>>(domain, codomain, seed as u64, 3));
+            check(&random_flat::<Fp<251>>(domain, codomain, seed as u64, 3));
+        }
+    }
+
+    #[test]
+    fn test_compose() {
+        let l = flat::<23>(&[1, 2, 3, 4], 2, 2);
+        let r = flat::<23>(&[2, 0, 1, 3], 2, 2);
+        let result = FpMatrix::from(&l).compose(&FpMatrix::from(&r));
+        assert_eq!(result, FpMatrix::from(&flat::<23>(&[4, 6, 10, 12], 2, 2)));
+    }
+
+    #[test]
+    fn test_stacking() {
+        let mut a = FpMatrix::from(&flat::<23>(&[1, 2], 1, 2));
+        let b = FpMatrix::from(&flat::<23>(&[3, 4], 1, 2));
+        a.block_sum(&b);
+        assert_eq!(
+            a,
+            FpMatrix::from(&flat::<23>(&[1, 0, 2, 0, 0, 3, 0, 4], 2, 4))
+        );
+
+        let mut c = FpMatrix::from(&flat::<23>(&[5, 6], 2, 1));
+        let mut d = FpMatrix::from(&flat::<23>(&[7, 8], 2, 1));
+        c.vstack(&mut d);
+        assert_eq!(c, FpMatrix::from(&flat::<23>(&[5, 6, 7, 8], 2, 2)));
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut m = FpMatrix::<23>::zero(2, 2);
+        m.set(1, 0, Fp(20));
+        m.add_at(1, 0, Fp(5));
+        assert_eq!(m.get(1, 0), Fp(2));
+        m.set_row(1, &[Fp(3), Fp(4)]);
+        assert_eq!(m.get_row(1), &[Fp(3), Fp(4)]);
+        assert!(m.is_row_non_zero(0));
+    }
+}
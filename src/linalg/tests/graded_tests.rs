@@ -2,10 +2,10 @@
 mod tests {
 
     use crate::linalg::field::F2;
-    use crate::linalg::graded::{BasisElement, GradedLinearMap, GradedVectorSpace};
+    use crate::linalg::graded::{BasisElement, GradedLinearMap, GradedSubspace, GradedVectorSpace};
     use crate::linalg::matrix::RModMorphism;
     use crate::linalg::row_matrix::RowMatrix;
-    use std::collections::HashMap; // Assuming F2 is implemented as a Field type.
+    use std::collections::BTreeMap;
 
     type G = i32; // Grading type
     type F = F2; // Field type
@@ -22,8 +22,8 @@ mod tests {
 
     #[test]
     fn test_graded_vector_space_from() {
-        // Test creating a GradedVectorSpace from a HashMap
-        let mut space = HashMap::default();
+        // Test creating a GradedVectorSpace from a BTreeMap
+        let mut space = BTreeMap::new();
         space.insert(0, vec![1, 2, 3]);
         space.insert(1, vec![4, 5]);
 
@@ -33,8 +33,8 @@ mod tests {
 
     #[test]
     fn test_graded_linear_map_from() {
-        // Test creating a GradedLinearMap from a HashMap
-        let mut map = HashMap::default();
+        // Test creating a GradedLinearMap from a BTreeMap
+        let mut map = BTreeMap::new();
         map.insert(0, RowMatrix::zero(2, 2));
         map.insert(1, RowMatrix::zero(3, 3));
 
@@ -45,7 +45,7 @@ mod tests {
     #[test]
     fn test_graded_linear_map_get_cokernel() {
         // Test get_cokernel() produces a correct GradedLinearMap
-        let mut map = HashMap::default();
+        let mut map = BTreeMap::new();
         map.insert(0, RowMatrix::zero(2, 3));
         map.insert(1, RowMatrix::zero(3, 4));
 
@@ -60,7 +60,7 @@ mod tests {
     #[test]
     fn test_graded_linear_map_get_kernel() {
         // Test get_kernel() produces a correct GradedLinearMap
-        let mut map = HashMap::default();
+        let mut map = BTreeMap::new();
         map.insert(0, RowMatrix::zero(3, 2));
         map.insert(1, RowMatrix::zero(4, 3));
 
@@ -75,11 +75,11 @@ mod tests {
     #[test]
     fn test_graded_linear_map_vstack() {
         // Test vstack combines matrices vertically for each grade
-        let mut map1 = HashMap::default();
+        let mut map1 = BTreeMap::new();
         map1.insert(0, RowMatrix::zero(2, 3));
         map1.insert(1, RowMatrix::zero(2, 4));
 
-        let mut map2 = HashMap::default();
+        let mut map2 = BTreeMap::new();
         map2.insert(0, RowMatrix::zero(2, 6));
         map2.insert(1, RowMatrix::zero(2, 2));
 
@@ -97,11 +97,11 @@ mod tests {
     #[test]
     fn test_graded_linear_map_block_sum() {
         // Test block_sum combines matrices block-wise
-        let mut map1 = HashMap::default();
+        let mut map1 = BTreeMap::new();
         map1.insert(0, RowMatrix::zero(2, 3));
         map1.insert(1, RowMatrix::zero(1, 2));
 
-        let mut map2 = HashMap::default();
+        let mut map2 = BTreeMap::new();
         map2.insert(0, RowMatrix::zero(2, 4));
         map2.insert(1, RowMatrix::zero(1, 3));
 
@@ -119,11 +119,11 @@ mod tests {
     #[test]
     fn test_graded_linear_map_compose() {
         // Test composing two graded linear maps
-        let mut map1 = HashMap::default();
+        let mut map1 = BTreeMap::new();
         map1.insert(0, RowMatrix::zero(2, 3));
         map1.insert(1, RowMatrix::zero(3, 2));
 
-        let mut map2 = HashMap::default();
+        let mut map2 = BTreeMap::new();
         map2.insert(0, RowMatrix::zero(3, 4));
         map2.insert(1, RowMatrix::zero(2, 5));
 
@@ -143,7 +143,7 @@ mod tests {
     #[test]
     fn test_graded_linear_map_pivots() {
         // Test pivots retrieves pivot positions
-        let mut map = HashMap::default();
+        let mut map = BTreeMap::new();
         map.insert(0, RowMatrix::identity(3));
         map.insert(1, RowMatrix::zero(4, 4));
 
@@ -159,7 +159,7 @@ mod tests {
     #[test]
     fn test_graded_linear_map_zero_codomain() {
         // Test zero_codomain produces a map with empty domain
-        let mut codomain_space = HashMap::default();
+        let mut codomain_space = BTreeMap::new();
         codomain_space.insert(0, vec![0; 3]);
         codomain_space.insert(1, vec![0; 2]);
 
@@ -171,4 +171,109 @@ mod tests {
         assert_eq!(zero_map.maps[&1].codomain, 0);
         assert_eq!(zero_map.maps[&1].domain, 2);
     }
+
+    #[test]
+    fn test_graded_linear_map_solve() {
+        // Identity in grade 0 and the zero map in grade 1
+        let mut map = BTreeMap::new();
+        map.insert(0, RowMatrix::identity(2));
+        map.insert(1, RowMatrix::zero(2, 2));
+        let linear_map: GradedLinearMap<G, F, M> = GradedLinearMap::from(map);
+
+        assert_eq!(linear_map.ranks()[&0], 2);
+        assert_eq!(linear_map.ranks()[&1], 0);
+        assert_eq!(linear_map.get_image().maps[&1].codomain, 0);
+
+        let mut v = BTreeMap::new();
+        v.insert(0, vec![F2(1), F2(0)]);
+        v.insert(1, vec![F2(0), F2(0)]);
+        v.insert(2, vec![F2(0)]);
+        assert!(linear_map.is_in_image(&v));
+        let solution = linear_map.solve(&v).unwrap();
+        assert_eq!(solution[&0], vec![F2(1), F2(0)]);
+        assert_eq!(solution[&1], vec![F2(0), F2(0)]);
+
+        v.insert(1, vec![F2(0), F2(1)]);
+        assert!(!linear_map.is_in_image(&v));
+        assert_eq!(linear_map.solve(&v), None);
+        let reduced = linear_map.reduce_modulo_image(&v);
+        assert_eq!(reduced[&0], vec![F2(0), F2(0)]);
+        assert_eq!(reduced[&1], vec![F2(0), F2(1)]);
+
+        v.insert(2, vec![F2(1)]);
+        assert!(!linear_map.is_in_image(&v));
+    }
+
+    /// Single map in grade 0 with the given `(domain, codomain)` entries set to one
+    fn grade_zero_map(
+        domain: usize,
+        codomain: usize,
+        ones: &[(usize, usize)],
+    ) -> GradedLinearMap<G, F, M> {
+        let mut m = RowMatrix::zero(domain, codomain);
+        for (i, j) in ones {
+            m.set(*i, *j, F2(1));
+        }
+        GradedLinearMap::from(BTreeMap::from([(0, m)]))
+    }
+
+    #[test]
+    fn test_graded_subspaces() {
+        let ambient: GradedVectorSpace<G, usize> =
+            GradedVectorSpace::from(BTreeMap::from([(0, vec![10, 11, 12])]));
+
+        // U = span(e0 + e1, e2) as the kernel of e0, e1 -> 1
+        let u = grade_zero_map(3, 1, &[(0, 0), (1, 0)]).kernel_subspace(&ambient);
+        assert_eq!(u.dimension_in_grade(&0), 2);
+        assert_eq!(u.space.0[&0], vec![10, 12]);
+        assert!(u.contains(&BTreeMap::from([(0, vec![F2(1), F2(1), F2(1)])])));
+        assert!(!u.contains(&BTreeMap::from([(0, vec![F2(0), F2(1), F2(0)])])));
+
+        // W = span(e1) keeps the name of e1
+        let w = GradedSubspace::new(&ambient, &grade_zero_map(1, 3, &[(0, 1)]));
+        assert_eq!(w.space.0[&0], vec![11]);
+
+        assert_eq!(u.intersection(&w).unwrap().dimension_in_grade(&0), 0);
+        assert_eq!(u.sum(&w).unwrap().dimension_in_grade(&0), 3);
+
+        let diagonal = grade_zero_map(1, 3, &[(0, 0), (0, 1), (0, 2)]).image_subspace(&ambient);
+        let meet = u.intersection(&diagonal).unwrap();
+        assert_eq!(meet.dimension_in_grade(&0), 1);
+        assert_eq!(meet.space.0[&0], vec![10]);
+        assert_eq!(u.sum(&diagonal).unwrap(), u);
+
+        // A grade without an inclusion is the zero subspace
+        let mut zero = w.clone();
+        zero.inclusion.maps.clear();
+        assert_eq!(u.sum(&zero).unwrap(), u);
+        assert_eq!(u.intersection(&zero).unwrap().dimension_in_grade(&0), 0);
+
+        let other: GradedVectorSpace<G, usize> =
+            GradedVectorSpace::from(BTreeMap::from([(0, vec![10, 11, 12, 13])]));
+        let other = grade_zero_map(1, 4, &[(0, 3)]).image_subspace(&other);
+        assert!(u.intersection(&other).is_err());
+        assert!(u.sum(&other).is_err());
+    }
+
+    #[test]
+    fn test_graded_quotient() {
+        let ambient: GradedVectorSpace<G, usize> =
+            GradedVectorSpace::from(BTreeMap::from([(0, vec![10, 11, 12]), (1, vec![20])]));
+
+        // The map vanishes in grade 1, so dividing out its kernel only leaves one of e0 and e1
+        let u = grade_zero_map(3, 1, &[(0, 0), (1, 0)]).kernel_subspace(&ambient);
+        assert_eq!(u.space.0[&1], vec![20]);
+        let quotient = u.quotient();
+        assert_eq!(quotient.dimension_in_grade(&0), 1);
+        assert_eq!(quotient.dimension_in_grade(&1), 0);
+        assert!([10, 11].contains(&quotient.space.0[&0][0]));
+        assert_eq!(quotient.kernel(), u);
+
+        // The cokernel of e -> e2 remembers that e0, e1 and e20 survive
+        let cokernel = grade_zero_map(1, 3, &[(0, 2)]).cokernel_quotient(&ambient);
+        assert_eq!(cokernel.space.0[&0], vec![10, 11]);
+        assert_eq!(cokernel.space.0[&1], vec![20]);
+        assert_eq!(cokernel.projection.maps[&0].get(2, 0), F2(0));
+        assert_eq!(cokernel.projection.maps[&0].get(2, 1), F2(0));
+    }
 }
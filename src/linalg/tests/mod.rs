@@ -1,5 +1,72 @@
 mod bigrading_tests;
+mod chain_complex_tests;
+mod echelon_tests;
+mod extension_field_tests;
+mod f2matrix_tests;
 mod field_tests;
 mod flatmatrix_tests;
+mod fpmatrix_tests;
 mod graded_tests;
+mod motivicgrading_tests;
+mod multigrading_tests;
+mod presentation_tests;
+mod rational_tests;
+mod ring_tests;
+mod ringmatrix_tests;
 mod rowmatrix_tests;
+mod sparsematrix_tests;
+
+use std::fmt::Debug;
+
+use super::{
+    field::Field,
+    flat_matrix::FlatMatrix,
+    matrix::{Matrix, RModMorphism},
+};
+
+/// Deterministic random matrix over a prime field, so the comparisons are reproducible.
+///
+/// Every entry is drawn with probability `density / 4` and is zero otherwise.
+pub(crate) fn random_flat<F: Field>(
+    domain: usize,
+    codomain: usize,
+    seed: u64,
+    density: u64,
+) -> FlatMatrix<F> {
+    let mut state = seed;
+    let mut m = FlatMatrix::zero(domain, codomain);
+    for i in 0..codomain {
+        for j in 0..domain {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            if (state >> 40) % 4 < density {
+                let value = (state >> 33) % F::get_characteristic() as u64;
+                m.set(j, i, F::parse(&value.to_string()).unwrap());
+            }
+        }
+    }
+    m
+}
+
+/// Checks every operation of the backend `M` against `FlatMatrix` on the same input
+pub(crate) fn check_against_flat<F: Field, M>(m: &FlatMatrix<F>)
+where
+    M: Matrix<F> + for<'a> From<&'a FlatMatrix<F>> + PartialEq + Debug,
+{
+    let backend = M::from(m);
+    assert_eq!(backend.pivots(), m.pivots());
+    assert_eq!(backend.first_non_zero_entry(), m.first_non_zero_entry());
+    assert_eq!(backend.transpose(), M::from(&m.transpose()));
+
+    let (mut a, mut b) = (m.clone(), M::from(m));
+    a.rref();
+    b.rref();
+    assert_eq!(b, M::from(&a));
+
+    assert_eq!(backend.kernel(), M::from(&m.kernel()));
+    assert_eq!(backend.cokernel(), M::from(&m.cokernel()));
+
+    let t = m.transpose();
+    assert_eq!(backend.compose(&M::from(&t)), M::from(&m.compose(&t)));
+}
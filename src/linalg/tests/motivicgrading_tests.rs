@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::linalg::grading::{Grading, MotivicGrading};
+
+    #[test]
+    fn test_arithmetic() {
+        let tau = MotivicGrading(0, -1);
+        let xi1 = MotivicGrading(2, 1);
+        assert_eq!(tau + xi1, MotivicGrading(2, 0));
+        assert_eq!(xi1 - tau, MotivicGrading(2, 2));
+        assert_eq!(xi1.integer_multiplication(3), MotivicGrading(6, 3));
+        assert_eq!(
+            vec![tau, xi1, xi1].into_iter().sum::<MotivicGrading>(),
+            MotivicGrading(4, 1)
+        );
+        assert_eq!((xi1.degree(), xi1.weight()), (2, 1));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(MotivicGrading::parse("(0, -1)"), Ok(MotivicGrading(0, -1)));
+        assert_eq!(MotivicGrading::parse("3,1"), Ok(MotivicGrading(3, 1)));
+        assert_eq!("(3, 1)".parse(), Ok(MotivicGrading(3, 1)));
+        assert!(MotivicGrading::parse("3").is_err());
+        assert_eq!(format!("{}", MotivicGrading(3, 1)), "(3, 1)");
+    }
+
+    #[test]
+    fn test_grading_trait() {
+        assert_eq!(MotivicGrading::degree_names(), vec!['t', 'w']);
+        assert_eq!(
+            MotivicGrading::chow_formulas(),
+            ("t-s".to_string(), "t-2w".to_string())
+        );
+        assert_eq!(MotivicGrading(3, 1).export_grade(), vec![3, 1]);
+    }
+
+    #[test]
+    fn test_within() {
+        let limit = MotivicGrading(6, 3);
+        assert_eq!(MotivicGrading(6, 3).coweight(), 3);
+        assert!(MotivicGrading(0, -3).within(limit));
+        assert!(!MotivicGrading(0, -4).within(limit));
+        assert!(MotivicGrading(4, 3).within(limit));
+        assert!(!MotivicGrading(7, 5).within(limit));
+        // tau, xi_1 and tau_0
+        assert!(MotivicGrading(0, -1).is_positive());
+        assert!(MotivicGrading(2, 1).is_positive());
+        assert!(MotivicGrading(1, 0).is_positive());
+        assert!(!MotivicGrading(0, 1).is_positive());
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use crate::linalg::grading::{Grading, LimitPolicy, MultiGrading};
+
+    #[test]
+    fn test_arithmetic() {
+        let a = MultiGrading([1, 2, 3]);
+        let b = MultiGrading([3, 4, -1]);
+        assert_eq!(a + b, MultiGrading([4, 6, 2]));
+        assert_eq!(a - b, MultiGrading([-2, -2, 4]));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(
+            vec![a, b, a].into_iter().sum::<MultiGrading<3>>(),
+            MultiGrading([5, 8, 5])
+        );
+        assert_eq!(a.integer_multiplication(-2), MultiGrading([-2, -4, -6]));
+    }
+
+    #[test]
+    fn test_parse() {
+        let a = MultiGrading([1, -2, 3]);
+        assert_eq!(format!("{}", a), "(1, -2, 3)");
+        assert_eq!("(1, -2, 3)".parse(), Ok(a));
+        assert_eq!(MultiGrading::<3>::parse(" 1,-2 , 3 "), Ok(a));
+        assert!(MultiGrading::<3>::parse("(1, 2)").is_err());
+        assert!(MultiGrading::<3>::parse("(1, 2, 3").is_err());
+        assert!(MultiGrading::<2>::parse("(1, x)").is_err());
+    }
+
+    #[test]
+    fn test_grading_trait() {
+        let a = MultiGrading([1, 2, 3, 4]);
+        assert_eq!(MultiGrading::<4>::degree_names(), vec!['t', 'w', 'u', 'v']);
+        assert_eq!(
+            MultiGrading::<4>::default_formulas(),
+            ("t-s".to_string(), "s".to_string())
+        );
+        assert_eq!(a.export_grade(), vec![1, 2, 3, 4]);
+        assert_eq!(MultiGrading::<4>::zero(), MultiGrading([0; 4]));
+        assert_eq!(MultiGrading::<4>::infty(), MultiGrading([i32::MAX; 4]));
+        assert!(MultiGrading([1, 5, 0]) < MultiGrading([2, 0, 0]));
+    }
+
+    #[test]
+    fn test_within() {
+        let limit = MultiGrading([3, 3, 3]);
+        assert!(MultiGrading([3, 0, -2]).within(limit));
+        assert!(!MultiGrading([0, 4, 0]).within(limit));
+        assert!(MultiGrading([0, 4, 0]) < limit);
+    }
+
+    #[test]
+    fn test_is_positive() {
+        assert!(MultiGrading([1, 0, 0]).is_positive());
+        assert!(!MultiGrading([0, 0, 0]).is_positive());
+        assert!(!MultiGrading([2, -1, 0]).is_positive());
+        assert!(!MultiGrading([-1, -1, -1]).is_positive());
+    }
+
+    #[test]
+    fn test_default_limit_policy() {
+        let limit = MultiGrading([4, 2, 0]);
+        assert_eq!(LimitPolicy::default().next(limit), MultiGrading([5, 3, 1]));
+        assert_eq!(LimitPolicy::<i32>::default().next(4), 5);
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        linalg::{
+            field::{CRing, Fp},
+            grading::UniGrading,
+            matrix::RModMorphism,
+            presentation::{GradedModuleMap, ModuleMap, Presentation},
+            ring::PrincipalIdealRing,
+            ring_matrix::RingMatrix,
+        },
+        polynomial::univariate::UnivariatePolynomial,
+    };
+
+    type P = UnivariatePolynomial<Fp<2>>;
+
+    fn p(input: &str) -> P {
+        <P as CRing>::parse(input).unwrap()
+    }
+
+    fn matrix(rows: Vec<Vec<P>>) -> RingMatrix<P> {
+        let mut m = RingMatrix::zero(rows[0].len(), rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            m.set_row(i, row);
+        }
+        m
+    }
+
+    #[test]
+    fn test_decompose() {
+        // F_2[t] / (t^2) + F_2[t] / (t^3)
+        let m = Presentation::new(matrix(vec![vec![p("t^2"), p("0")], vec![p("0"), p("t^3")]]));
+        let decomposition = m.decompose();
+        assert_eq!(decomposition.invariant_factors, vec![p("t^2"), p("t^3")]);
+        assert_eq!(decomposition.free_rank, 0);
+
+        assert!(m.is_zero_element(&[p("t^2+t^4"), p("t^3")]));
+        assert!(!m.is_zero_element(&[p("t"), p("0")]));
+        assert!(!m.is_zero_element(&[p("0"), p("t^2")]));
+        assert!(Presentation::new(matrix(vec![vec![p("1")]])).is_zero());
+        assert!(!Presentation::<P>::free(1).is_zero());
+    }
+
+    #[test]
+    fn test_module_map() {
+        // Multiplication by t on F_2[t] / (t^3)
+        let torsion = Presentation::new(matrix(vec![vec![p("t^3")]]));
+        let t =
+            ModuleMap::new(torsion.clone(), torsion.clone(), matrix(vec![vec![p("t")]])).unwrap();
+
+        let kernel = t.kernel();
+        assert_eq!(kernel.codomain, torsion);
+        assert_eq!(kernel.domain.decompose().invariant_factors, vec![p("t")]);
+        assert!(kernel.codomain.is_zero_element(&[p("t^3")]));
+        assert!(!kernel
+            .codomain
+            .is_zero_element(kernel.map.transpose().get_row(0)));
+
+        let cokernel = t.cokernel();
+        assert_eq!(
+            cokernel.codomain.decompose().invariant_factors,
+            vec![p("t")]
+        );
+
+        // Multiplication by t is not well defined from F_2[t] / (t) to F_2[t] / (t^2)
+        assert!(ModuleMap::new(
+            Presentation::new(matrix(vec![vec![p("t")]])),
+            Presentation::new(matrix(vec![vec![p("t^2")]])),
+            matrix(vec![vec![p("1")]])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_free_kernel() {
+        // F_2[t]^2 -> F_2[t], (x, y) -> t x + t^2 y has kernel generated by (t, 1)
+        let map = ModuleMap::new(
+            Presentation::free(2),
+            Presentation::free(1),
+            matrix(vec![vec![p("t"), p("t^2")]]),
+        )
+        .unwrap();
+        let kernel = map.kernel();
+        assert_eq!(kernel.domain.decompose().free_rank, 1);
+        assert_eq!(kernel.domain.decompose().invariant_factors, vec![]);
+        let generator = kernel.map.transpose().get_row(0).to_vec();
+        assert_eq!(
+            generator[0].clone() * p("t") + generator[1].clone() * p("t^2"),
+            p("0")
+        );
+        assert_eq!(
+            generator[1].clone() * generator[1].canonical_associate().1,
+            generator[1].canonical_associate().0
+        );
+
+        let mut maps = BTreeMap::new();
+        maps.insert(0 as UniGrading, map);
+        let graded: GradedModuleMap<UniGrading, P> = maps.into();
+        let cokernel = graded.get_cokernel();
+        assert_eq!(
+            cokernel.maps[&0].codomain.decompose().invariant_factors,
+            vec![p("t")]
+        );
+        assert_eq!(graded.get_kernel().maps[&0], graded.maps[&0].kernel());
+    }
+
+    #[test]
+    fn test_high_degree() {
+        // Reducing this goes through products of degree above 20
+        let m = Presentation::new(matrix(vec![
+            vec![p("t^9+1"), p("t^10")],
+            vec![p("t^12"), p("t^13+t")],
+        ]));
+        let decomposition = m.decompose();
+        assert_eq!(decomposition.invariant_factors, vec![p("t^13+t^10+t")]);
+        assert_eq!(decomposition.free_rank, 0);
+    }
+}
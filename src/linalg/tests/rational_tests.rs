@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use crate::linalg::{
+        field::{CRing, Field},
+        flat_matrix::FlatMatrix,
+        matrix::{Matrix, RModMorphism},
+        rational::Q,
+        row_matrix::RowMatrix,
+        sparse_matrix::SparseMatrix,
+    };
+
+    #[test]
+    fn test_normal_form() {
+        assert_eq!(Q::new(2, 4), Q::new(1, 2));
+        assert_eq!(Q::new(3, -6), Q::new(-1, 2));
+        assert_eq!(Q::new(0, -5), Q::zero());
+        assert_eq!(Q::new(-4, -2).numerator(), 2);
+        assert_eq!(Q::new(-4, -2).denominator(), 1);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Q::new(1, 3);
+        let b = Q::new(1, 6);
+        assert_eq!(a + b, Q::new(1, 2));
+        assert_eq!(a - b, Q::new(1, 6));
+        assert_eq!(b - a, Q::new(-1, 6));
+        assert_eq!(a * b, Q::new(1, 18));
+        assert_eq!(-a, Q::new(-1, 3));
+        assert_eq!(a.inv(), Some(Q::from(3)));
+        assert_eq!(Q::new(-2, 5).inv(), Some(Q::new(-5, 2)));
+        assert_eq!(Q::zero().inv(), None);
+        assert_eq!(
+            (1..=10).map(|n| Q::new(1, n)).sum::<Q>(),
+            Q::new(7381, 2520)
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Q::parse("3/6").unwrap(), Q::new(1, 2));
+        assert_eq!(Q::parse(" -7 / 2 ").unwrap(), Q::new(-7, 2));
+        assert_eq!(Q::parse("5").unwrap(), Q::from(5));
+        assert_eq!(format!("{:?}", Q::new(6, -4)), "-3/2");
+        assert_eq!(format!("{:?}", Q::from(4)), "4");
+        assert!(Q::parse("1/0").is_err());
+        assert!(Q::parse("a/2").is_err());
+        assert!(Q::parse("0.5").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_overflow_panics() {
+        let big = Q::from(i64::MAX);
+        let _ = big * big * big;
+    }
+
+    #[test]
+    fn test_extreme_normal_form() {
+        assert_eq!(Q::new(i128::MIN, i128::MIN), Q::one());
+        assert_eq!(Q::new(i128::MIN, 2).numerator(), i128::MIN / 2);
+        assert_eq!(Q::new(0, i128::MIN), Q::zero());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extreme_normal_form_overflow() {
+        let _ = Q::new(i128::MIN, -1);
+    }
+
+    #[test]
+    fn test_as_usize() {
+        assert_eq!(Q::from(5).as_usize(), 5);
+        assert_eq!(Q::zero().as_usize(), 0);
+        assert_eq!(Q::new(7, 2).as_usize(), 3);
+        assert_eq!(Q::new(-7, 2).as_usize(), 0);
+        assert_eq!(Q::from(-1).as_usize(), 0);
+        assert_eq!(Q::new(i128::MAX, 1).as_usize(), usize::MAX);
+        assert_eq!(Q::new(-1, 2).export_value(), "-1/2");
+    }
+
+    // (7, 3, 21) is 21 times (1/3, 1/7, 1), which rounding in f64 obscures
+    fn proportional_rows() -> Vec<Vec<Q>> {
+        vec![
+            vec![Q::new(1, 3), Q::new(1, 7), Q::one()],
+            vec![Q::from(7), Q::from(3), Q::from(21)],
+            vec![Q::new(1, 10), Q::new(1, 10), Q::new(3, 10)],
+        ]
+    }
+
+    fn from_rows<M: RModMorphism<Q>>(rows: &[Vec<Q>]) -> M {
+        let mut m = M::zero(rows[0].len(), rows.len());
+        for (i, r) in rows.iter().enumerate() {
+            m.set_row(i, r);
+        }
+        m
+    }
+
+    #[test]
+    fn test_exact_kernel_on_backends() {
+        let rows = proportional_rows();
+        let flat: FlatMatrix<Q> = from_rows(&rows);
+
+        let kernel = flat.kernel();
+        assert_eq!(kernel.codomain(), 1);
+        assert_eq!(kernel.get_row(0), vec![Q::one(), Q::zero(), Q::new(-1, 3)]);
+        assert_eq!(flat.compose(&kernel.transpose()), FlatMatrix::zero(1, 3));
+
+        assert_eq!(
+            from_rows::<RowMatrix<Q>>(&rows).kernel().get_row(0),
+            kernel.get_row(0)
+        );
+        assert_eq!(
+            from_rows::<SparseMatrix<Q>>(&rows).kernel().get_row(0),
+            kernel.get_row(0)
+        );
+    }
+}
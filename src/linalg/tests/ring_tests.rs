@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use crate::linalg::{
+        field::CRing,
+        ring::{PrincipalIdealRing, ZLocal, Zpk},
+    };
+
+    fn check_xgcd<R: PrincipalIdealRing + Copy>(a: R, b: R) {
+        let (g, s, t, u, v) = R::xgcd(a, b);
+        assert_eq!(s * a + t * b, g, "{:?} {:?}", a, b);
+        assert!((u * a + v * b).is_zero(), "{:?} {:?}", a, b);
+        assert_eq!(s * v - t * u, R::one(), "{:?} {:?}", a, b);
+        assert!(R::divide(a, g).is_some() && R::divide(b, g).is_some());
+    }
+
+    #[test]
+    fn test_integer_xgcd() {
+        for a in -12i64..=12 {
+            for b in -12i64..=12 {
+                check_xgcd(a, b);
+            }
+        }
+        assert_eq!(i64::xgcd(12, 18).0.abs(), 6);
+    }
+
+    #[test]
+    fn test_integer_division() {
+        assert_eq!(i64::divide(12, -4), Some(-3));
+        assert_eq!(i64::divide(12, 5), None);
+        assert_eq!(i64::divide(0, 0), Some(0));
+        assert_eq!(i64::divide(1, 0), None);
+        assert_eq!(i64::rem(-7, 3), 2);
+        assert_eq!((-6i64).canonical_associate(), (6, -1));
+        assert_eq!(3i64.annihilator(), 0);
+        assert_eq!(0i64.annihilator(), 1);
+    }
+
+    #[test]
+    fn test_zpk() {
+        type Z8 = Zpk<2, 3>;
+        assert_eq!(Z8::MODULUS, 8);
+        assert_eq!(Z8::parse("-1"), Ok(Zpk(7)));
+        assert_eq!(Zpk::<2, 3>(6).valuation(), 1);
+        assert_eq!(Z8::zero().valuation(), 3);
+
+        for a in 0..8 {
+            for b in 0..8 {
+                check_xgcd(Zpk::<2, 3>(a), Zpk(b));
+            }
+        }
+
+        assert_eq!(Z8::divide(Zpk(6), Zpk(2)), Some(Zpk(3)));
+        assert_eq!(Z8::divide(Zpk(4), Zpk(6)), Some(Zpk(6)));
+        assert_eq!(Z8::divide(Zpk(2), Zpk(4)), None);
+        assert_eq!(Z8::rem(Zpk(7), Zpk(4)), Zpk(3));
+        assert_eq!(Zpk::<2, 3>(6).canonical_associate().0, Zpk(2));
+        assert_eq!(Zpk::<2, 3>(2).annihilator(), Zpk(4));
+        assert_eq!(Zpk::<2, 3>(3).annihilator(), Zpk(0));
+        assert!(Zpk::<2, 3>(5).is_unit());
+        assert!(!Zpk::<2, 3>(4).is_unit());
+    }
+
+    #[test]
+    fn test_localization() {
+        type Z3 = ZLocal<3>;
+        let x = |num, den| Z3::new(num, den).unwrap();
+        assert!(Z3::new(1, 3).is_none());
+        assert!(Z3::parse("1/3").is_err());
+        assert_eq!(Z3::parse("5/2"), Ok(x(5, 2)));
+        assert_eq!(x(18, 5).valuation(), 2);
+
+        for a in -6..=6 {
+            for b in [1, 2, 4, 5] {
+                check_xgcd(x(a, b), x(b, 1));
+                check_xgcd(x(3 * a, 1), x(9, b));
+            }
+        }
+
+        assert_eq!(Z3::divide(x(9, 1), x(6, 1)), Some(x(3, 2)));
+        assert_eq!(Z3::divide(x(3, 1), x(9, 1)), None);
+        assert_eq!(Z3::rem(x(1, 2), x(9, 1)), x(5, 1));
+        assert_eq!(x(-6, 5).canonical_associate().0, x(3, 1));
+        assert!(x(2, 5).is_unit());
+    }
+}
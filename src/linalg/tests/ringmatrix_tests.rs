@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::linalg::{
+        graded::GradedLinearMap,
+        grading::UniGrading,
+        matrix::RModMorphism,
+        ring::{PrincipalIdealRing, ZLocal, Zpk},
+        ring_matrix::RingMatrix,
+    };
+
+    fn matrix<R: PrincipalIdealRing>(rows: Vec<Vec<R>>) -> RingMatrix<R> {
+        let mut m = RingMatrix::zero(rows[0].len(), rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            m.set_row(i, row);
+        }
+        m
+    }
+
+    fn diagonal<R: PrincipalIdealRing>(domain: usize, codomain: usize, d: &[R]) -> RingMatrix<R> {
+        let mut m = RingMatrix::zero(domain, codomain);
+        for (i, x) in d.iter().enumerate() {
+            m.set(i, i, x.clone());
+        }
+        m
+    }
+
+    #[test]
+    fn test_smith_normal_form() {
+        let m = matrix::<i64>(vec![vec![2, 4, 4], vec![-6, 6, 12], vec![10, -4, -16]]);
+        let smith = m.smith_normal_form();
+        assert_eq!(smith.diagonal, vec![2, 6, 12]);
+        assert_eq!(
+            smith.left.compose(&m).compose(&smith.right),
+            diagonal(3, 3, &smith.diagonal)
+        );
+        assert_eq!(m.rank(), 3);
+    }
+
+    #[test]
+    fn test_smith_normal_form_rectangular() {
+        let m = matrix::<i64>(vec![vec![4, 6], vec![6, 9], vec![2, 3]]);
+        let smith = m.smith_normal_form();
+        assert_eq!(smith.diagonal, vec![1, 0]);
+        assert_eq!(
+            smith.left.compose(&m).compose(&smith.right),
+            diagonal(2, 3, &smith.diagonal)
+        );
+
+        let m = matrix::<Zpk<2, 3>>(vec![
+            vec![Zpk(6), Zpk(4), Zpk(2)],
+            vec![Zpk(4), Zpk(0), Zpk(0)],
+        ]);
+        let smith = m.smith_normal_form();
+        assert_eq!(smith.diagonal, vec![Zpk(2), Zpk(4)]);
+        assert_eq!(
+            smith.left.compose(&m).compose(&smith.right),
+            diagonal(3, 2, &smith.diagonal)
+        );
+    }
+
+    #[test]
+    fn test_hermite_normal_form() {
+        let m = matrix::<i64>(vec![vec![2, 3, 6], vec![4, 5, 7], vec![6, 7, 8]]);
+        let (h, u) = m.hermite_normal_form();
+        assert_eq!(u.compose(&m), h);
+        assert_eq!(
+            h,
+            matrix(vec![vec![2, 0, -9], vec![0, 1, 5], vec![0, 0, 0]])
+        );
+
+        let m = matrix::<i64>(vec![vec![3, 1], vec![6, 5]]);
+        let (h, u) = m.hermite_normal_form();
+        assert_eq!(u.compose(&m), h);
+        assert_eq!(h, matrix(vec![vec![3, 1], vec![0, 3]]));
+    }
+
+    #[test]
+    fn test_integer_kernel_and_cokernel() {
+        // Z^3 -> Z^2, (x, y, z) -> (2x + 4y, 6z)
+        let m = matrix::<i64>(vec![vec![2, 4, 0], vec![0, 0, 6]]);
+
+        let kernel = m.kernel();
+        assert_eq!(kernel.annihilators, vec![0]);
+        assert_eq!(kernel.generators.codomain, 1);
+        assert!(m
+            .compose(&kernel.generators.transpose())
+            .data
+            .iter()
+            .all(|x| *x == 0));
+        let k = kernel.generators.get_row(0);
+        assert_eq!(k[0] * k[0] + k[1] * k[1] + k[2] * k[2], 5);
+
+        let cokernel = m.cokernel();
+        assert_eq!(cokernel.invariant_factors, vec![2, 6]);
+        assert_eq!(cokernel.free_rank, 0);
+        assert!(cokernel
+            .projection
+            .compose(&m)
+            .data
+            .chunks(3)
+            .zip(&cokernel.invariant_factors)
+            .all(|(row, d)| row.iter().all(|x| x % d == 0)));
+    }
+
+    #[test]
+    fn test_torsion_kernel() {
+        // Multiplication by 2 on Z/8 has kernel generated by 4, of order 2
+        let m = matrix::<Zpk<2, 3>>(vec![vec![Zpk(2)]]);
+        let kernel = m.kernel();
+        assert_eq!(kernel.generators.get_row(0), vec![Zpk(4)]);
+        assert_eq!(kernel.annihilators, vec![Zpk(2)]);
+        let cokernel = m.cokernel();
+        assert_eq!(cokernel.invariant_factors, vec![Zpk(2)]);
+        assert_eq!(cokernel.free_rank, 0);
+
+        // The zero map Z/8 -> Z/8 has free kernel and cokernel
+        let m = matrix::<Zpk<2, 3>>(vec![vec![Zpk(0)]]);
+        assert_eq!(m.kernel().annihilators, vec![Zpk(0)]);
+        assert_eq!(m.cokernel().free_rank, 1);
+    }
+
+    #[test]
+    fn test_local_cokernel() {
+        // Over Z_(3) the cokernel of diag(2, 6, 0) is Z/3 + Z_(3)
+        let x = |n| ZLocal::<3>::new(n, 1).unwrap();
+        let m = diagonal(3, 3, &[x(6), x(2), x(0)]);
+        let cokernel = m.cokernel();
+        assert_eq!(cokernel.invariant_factors, vec![x(3)]);
+        assert_eq!(cokernel.free_rank, 1);
+        assert_eq!(cokernel.projection.codomain, 2);
+    }
+
+    #[test]
+    fn test_graded_ring_cokernel() {
+        let mut maps = BTreeMap::new();
+        maps.insert(0 as UniGrading, matrix::<i64>(vec![vec![2]]));
+        maps.insert(1, matrix::<i64>(vec![vec![1, 0], vec![0, 0]]));
+        let map: GradedLinearMap<UniGrading, i64, RingMatrix<i64>> = maps.into();
+
+        let cokernel = map.get_ring_cokernel();
+        assert_eq!(cokernel[&0].invariant_factors, vec![2]);
+        assert_eq!(cokernel[&1].invariant_factors, Vec::<i64>::new());
+        assert_eq!(cokernel[&1].free_rank, 1);
+
+        let kernel = map.get_ring_kernel();
+        assert_eq!(kernel[&0].generators.codomain, 0);
+        assert_eq!(kernel[&1].annihilators, vec![0]);
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use crate::linalg::{
+        field::{CRing, Field, Fp, F2},
+        flat_matrix::FlatMatrix,
+        matrix::{Matrix, RModMorphism},
+        sparse_matrix::SparseMatrix,
+        tests::{check_against_flat, random_flat},
+    };
+
+    type TestField = Fp<23>;
+
+    // Mostly zero matrices, like the coaction matrices this backend is meant for
+    fn random_sparse<F: Field>(domain: usize, codomain: usize, seed: u64) -> FlatMatrix<F> {
+        random_flat(domain, codomain, seed, 1)
+    }
+
+    #[test]
+    fn test_random_against_flat() {
+        for (seed, &(domain, codomain)) in [(0, 3), (3, 0), (5, 7), (20, 20), (33, 12), (12, 40)]
+            .iter()
+            .enumerate()
+        {
+            check_against_flat::<TestField, SparseMatrix<TestField>>(&random_sparse(
+                domain,
+                codomain,
+                seed as u64,
+            ));
+            check_against_flat::<F2, SparseMatrix<F2>>(&random_sparse(
+                domain,
+                codomain,
+                seed as u64,
+            ));
+        }
+    }
+
+    #[test]
+    fn test_stacking_against_flat() {
+        let mut a = random_sparse::<TestField>(6, 4, 1);
+        let mut b = random_sparse::<TestField>(6, 3, 2);
+        let c = random_sparse::<TestField>(2, 5, 3);
+        let (mut sa, mut sb, sc) = (
+            SparseMatrix::from(&a),
+            SparseMatrix::from(&b),
+            SparseMatrix::from(&c),
+        );
+
+        a.vstack(&mut b);
+        sa.vstack(&mut sb);
+        assert_eq!(sa, SparseMatrix::from(&a));
+
+        a.block_sum(&c);
+        sa.block_sum(&sc);
+        assert_eq!(sa, SparseMatrix::from(&a));
+    }
+
+    #[test]
+    fn test_set_keeps_rows_sparse() {
+        let mut m = SparseMatrix::<TestField>::zero(10, 2);
+        m.set(7, 0, TestField { 0: 3 });
+        m.set(2, 0, TestField { 0: 4 });
+        assert_eq!(
+            m.data[0],
+            vec![(2, TestField { 0: 4 }), (7, TestField { 0: 3 })]
+        );
+
+        m.add_at(7, 0, TestField { 0: 20 });
+        assert_eq!(m.data[0], vec![(2, TestField { 0: 4 })]);
+        assert_eq!(m.get(7, 0), TestField::zero());
+
+        m.set_row(1, &m.get_row(0));
+        assert_eq!(m.non_zero_entries(), 2);
+        assert!(m.is_row_non_zero(1));
+    }
+
+    #[test]
+    fn test_identity() {
+        let id = SparseMatrix::<TestField>::identity(4);
+        assert_eq!(id, SparseMatrix::from(&FlatMatrix::identity(4)));
+        assert_eq!(id.kernel(), SparseMatrix::zero(4, 0));
+        assert_eq!(id.cokernel(), SparseMatrix::zero(4, 0));
+    }
+
+    #[test]
+    fn test_rref_with_transform() {
+        // SparseMatrix uses the default implementation through [A | I]
+        for (seed, &(domain, codomain)) in [(4, 6), (6, 4), (0, 2), (7, 7)].iter().enumerate() {
+            let flat = random_sparse::<TestField>(domain, codomain, seed as u64);
+            let matrix = SparseMatrix::from(&flat);
+
+            let mut reduced = matrix.clone();
+            let (t, t_inv) = reduced.rref_with_transform();
+
+            let mut expected = flat.clone();
+            expected.rref();
+            assert_eq!(reduced, SparseMatrix::from(&expected));
+            assert_eq!(t.compose(&matrix), reduced);
+            assert_eq!(t_inv.compose(&reduced), matrix);
+            assert_eq!(t.compose(&t_inv), SparseMatrix::identity(codomain));
+        }
+    }
+
+    #[test]
+    fn test_solve_against_flat() {
+        // SparseMatrix uses the default implementations through the trait
+        for (seed, &(domain, codomain)) in [(4, 6), (6, 4), (0, 2), (7, 7)].iter().enumerate() {
+            let flat = random_sparse::<TestField>(domain, codomain, seed as u64);
+            let sparse = SparseMatrix::from(&flat);
+            assert_eq!(sparse.rank(), flat.rank());
+            assert_eq!(sparse.image(), SparseMatrix::from(&flat.image()));
+
+            // The first column is the image of the first basis vector
+            let v: Vec<TestField> = (0..codomain)
+                .map(|i| match domain {
+                    0 => TestField::zero(),
+                    _ => flat.get(0, i),
+                })
+                .collect();
+            let w: Vec<TestField> = (0..codomain).map(|i| TestField { 0: i as u8 }).collect();
+            for v in [v, w] {
+                assert_eq!(sparse.solve(&v), flat.solve(&v));
+                assert_eq!(sparse.reduce_modulo_image(&v), flat.reduce_modulo_image(&v));
+            }
+        }
+    }
+}
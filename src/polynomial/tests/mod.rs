@@ -1 +1,2 @@
 mod multivariate_tests;
+mod univariate_tests;
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        linalg::{
+            field::{CRing, Fp},
+            ring::PrincipalIdealRing,
+        },
+        polynomial::{polynomial::Polynomial, univariate::UnivariatePolynomial},
+    };
+
+    type P = UnivariatePolynomial<Fp<3>>;
+
+    fn p(input: &str) -> P {
+        <P as CRing>::parse(input).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(p("t^2+1"), P::new(&[Fp(1), Fp(0), Fp(1)]));
+        assert_eq!(p("2*t^3 - t + 4"), P::new(&[Fp(1), Fp(2), Fp(0), Fp(2)]));
+        assert_eq!(p("τ^2-τ"), p("t^2+2t"));
+        assert_eq!(p("0"), <P as CRing>::zero());
+        assert!(<P as CRing>::parse("").is_err());
+        assert_eq!(format!("{:?}", p("2t^3+t+1")), "2t^3+t+1");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(p("t+1") * p("t-1"), p("t^2-1"));
+        assert_eq!(p("t+1") + p("2t"), p("1"));
+        assert_eq!(p("t^2+1").eval(Fp(2)), Fp(2));
+        assert_eq!(p("t^3").degree(), Some(3));
+        assert_eq!(<P as CRing>::zero().degree(), None);
+
+        let (q, r) = p("t^3+2t+1").div_rem(&p("t^2+1"));
+        assert_eq!(q, p("t"));
+        assert_eq!(r, p("t+1"));
+    }
+
+    #[test]
+    fn test_unbounded_degree() {
+        assert_eq!((p("t^40+1") * p("t^40-1")).degree(), Some(80));
+        assert_eq!(p("t^40+1") - p("t^40"), p("1"));
+        assert_eq!(p("t^40+1").coefficients().len(), 41);
+        assert!(<P as CRing>::is_zero(&(p("t^9") - p("t^9"))));
+        assert_eq!(P::new(&[Fp(1), Fp(0), Fp(0)]).degree(), Some(0));
+    }
+
+    #[test]
+    fn test_euclidean() {
+        let (a, b) = (p("t^3-t"), p("t^2+2t+1"));
+        let (g, s, t, u, v) = P::xgcd(a.clone(), b.clone());
+        assert_eq!(s.clone() * a.clone() + t.clone() * b.clone(), g);
+        assert_eq!(g.canonical_associate().0, p("t+1"));
+        assert!(<P as CRing>::is_zero(&(u.clone() * a + v.clone() * b)));
+        assert_eq!(s * v - t * u, <P as CRing>::one());
+
+        assert_eq!(P::divide(p("t^2-1"), p("t+1")), Some(p("t-1")));
+        assert_eq!(P::divide(p("t^2"), p("t+1")), None);
+        assert_eq!(P::rem(p("t^2"), p("t+1")), p("1"));
+        assert_eq!(p("2t+1").canonical_associate().0, p("t+2"));
+        assert!(p("2").is_unit());
+        assert!(!p("t").is_unit());
+    }
+}
@@ -1 +1,338 @@
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+use crate::linalg::{
+    field::{CRing, Field},
+    ring::PrincipalIdealRing,
+};
+
+use super::polynomial::Polynomial;
+
+/// Polynomial in one variable `t`, stored as the coefficients of `1, t, t^2, ...`
+/// without trailing zeros, so the zero polynomial has no coefficients.
+///
+/// It serves as coefficients of `RingMatrix` and `Presentation`, comodules and their
+/// coactions are only defined over a `Field` so far.
+#[derive(Clone, PartialEq)]
+pub struct UnivariatePolynomial<F: Field>(Vec<F>);
+
+impl<F: Field> UnivariatePolynomial<F> {
+    /// Takes the coefficients of `1, t, t^2, ...`
+    pub fn new(coeffs: &[F]) -> Self {
+        let mut poly = Self(coeffs.to_vec());
+        poly.trim();
+        poly
+    }
+
+    /// The variable `t`
+    pub fn variable() -> Self {
+        Self::monomial(F::one(), 1)
+    }
+
+    pub fn monomial(c: F, degree: usize) -> Self {
+        if c.is_zero() {
+            return <Self as CRing>::zero();
+        }
+        let mut coeffs = vec![F::zero(); degree + 1];
+        coeffs[degree] = c;
+        Self(coeffs)
+    }
+
+    /// Coefficients of `1, t, t^2, ...` up to the leading one
+    pub fn coefficients(&self) -> &[F] {
+        &self.0
+    }
+
+    fn trim(&mut self) {
+        while self.0.last().is_some_and(|c| c.is_zero()) {
+            self.0.pop();
+        }
+    }
+
+    /// Degree, `None` for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.0.len().checked_sub(1)
+    }
+
+    pub fn leading_coefficient(&self) -> F {
+        self.0.last().copied().unwrap_or(F::zero())
+    }
+
+    /// Quotient and remainder of the division by a non-zero `rhs`
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let d = rhs.degree().expect("Division by the zero polynomial");
+        let inv = rhs.0[d].inv().expect("Leading coefficient is non-zero");
+        let mut r = self.0.clone();
+        let mut q = vec![F::zero(); r.len().saturating_sub(d)];
+        for e in (d..r.len()).rev() {
+            let c = r[e] * inv;
+            if c.is_zero() {
+                continue;
+            }
+            q[e - d] = c;
+            for i in 0..=d {
+                r[e - d + i] -= c * rhs.0[i];
+            }
+        }
+        (Self::new(&q), Self::new(&r))
+    }
+
+    /// Parses a single term such as `3`, `t`, `2t^3` or `2*t^3`
+    fn parse_term(term: &str) -> Result<Self, String> {
+        let err = || format!("Polynomial: {} could not be parsed", term);
+        match term.find(|c: char| c.is_alphabetic()) {
+            None => Ok(Self::monomial(F::parse(term)?, 0)),
+            Some(i) => {
+                let scalar = term[..i].strip_suffix('*').unwrap_or(&term[..i]);
+                let scalar = match scalar {
+                    "" => F::one(),
+                    s => F::parse(s)?,
+                };
+                let exponent = match term[i..].split_once('^') {
+                    None => 1,
+                    Some((_, e)) => e.parse().map_err(|_| err())?,
+                };
+                Ok(Self::monomial(scalar, exponent))
+            }
+        }
+    }
+}
+
+impl<F: Field> Add for UnivariatePolynomial<F> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<F: Field> Sub for UnivariatePolynomial<F> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<F: Field> Neg for UnivariatePolynomial<F> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        self.0.iter_mut().for_each(|c| *c = -*c);
+        self
+    }
+}
+
+impl<F: Field> Mul for UnivariatePolynomial<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (l, r) = match (self.degree(), rhs.degree()) {
+            (Some(l), Some(r)) => (l, r),
+            _ => return <Self as CRing>::zero(),
+        };
+        let mut product = vec![F::zero(); l + r + 1];
+        for i in 0..=l {
+            if self.0[i].is_zero() {
+                continue;
+            }
+            for j in 0..=r {
+                product[i + j] += self.0[i] * rhs.0[j];
+            }
+        }
+        Self::new(&product)
+    }
+}
+
+impl<F: Field> AddAssign for UnivariatePolynomial<F> {
+    fn add_assign(&mut self, rhs: Self) {
+        if self.0.len() < rhs.0.len() {
+            self.0.resize(rhs.0.len(), F::zero());
+        }
+        for (c, r) in self.0.iter_mut().zip(rhs.0) {
+            *c += r;
+        }
+        self.trim();
+    }
+}
+
+impl<F: Field> SubAssign for UnivariatePolynomial<F> {
+    fn sub_assign(&mut self, rhs: Self) {
+        if self.0.len() < rhs.0.len() {
+            self.0.resize(rhs.0.len(), F::zero());
+        }
+        for (c, r) in self.0.iter_mut().zip(rhs.0) {
+            *c -= r;
+        }
+        self.trim();
+    }
+}
+
+impl<F: Field> MulAssign for UnivariatePolynomial<F> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<F: Field> std::iter::Sum for UnivariatePolynomial<F> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(<Self as CRing>::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<F: Field> Debug for UnivariatePolynomial<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let terms: Vec<String> = (0..self.0.len())
+            .rev()
+            .filter(|&i| !self.0[i].is_zero())
+            .map(|i| {
+                let c = self.0[i];
+                match (c == F::one(), i) {
+                    (_, 0) => format!("{:?}", c),
+                    (true, 1) => "t".to_string(),
+                    (false, 1) => format!("{:?}t", c),
+                    (true, i) => format!("t^{}", i),
+                    (false, i) => format!("{:?}t^{}", c, i),
+                }
+            })
+            .collect();
+        if terms.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", terms.join("+"))
+        }
+    }
+}
+
+impl<F: Field> CRing for UnivariatePolynomial<F> {
+    fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn one() -> Self {
+        Self::monomial(F::one(), 0)
+    }
+
+    fn zero() -> Self {
+        Self(vec![])
+    }
+
+    /// Parses sums of terms like `t^2+2t-1`, the variable can have any alphabetic name
+    fn parse(input: &str) -> Result<Self, String> {
+        let input: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+        if input.is_empty() {
+            return Err("Polynomial: empty input could not be parsed".to_owned());
+        }
+
+        let mut sum = <Self as CRing>::zero();
+        let mut start = 0;
+        for (i, c) in input.char_indices().chain([(input.len(), '+')]) {
+            // A sign directly after the exponent marker belongs to the term
+            if (c == '+' || c == '-') && i > start && !input[..i].ends_with('^') {
+                let term = &input[start..i];
+                sum += match term.strip_prefix('-') {
+                    Some(t) => -Self::parse_term(t)?,
+                    None => Self::parse_term(term.strip_prefix('+').unwrap_or(term))?,
+                };
+                start = i;
+            }
+        }
+        Ok(sum)
+    }
+}
+
+impl<F: Field> Polynomial<F> for UnivariatePolynomial<F> {
+    fn eval(&self, scalar: F) -> F {
+        self.0
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, c| acc * scalar + *c)
+    }
+
+    fn scalar_mult(&mut self, scalar: F) {
+        self.0.iter_mut().for_each(|c| *c *= scalar);
+        self.trim();
+    }
+
+    fn is_zero(&self) -> bool {
+        <Self as CRing>::is_zero(self)
+    }
+
+    fn one() -> Self {
+        <Self as CRing>::one()
+    }
+
+    fn zero() -> Self {
+        <Self as CRing>::zero()
+    }
+
+    fn parse(input: &str) -> Result<Self, String> {
+        <Self as CRing>::parse(input)
+    }
+}
+
+/// `F[t]` is a Euclidean domain, the norm is the degree
+impl<F: Field> PrincipalIdealRing for UnivariatePolynomial<F> {
+    fn xgcd(a: Self, b: Self) -> (Self, Self, Self, Self, Self) {
+        let (one, zero) = (<Self as CRing>::one, <Self as CRing>::zero);
+        if <Self as CRing>::is_zero(&a) && <Self as CRing>::is_zero(&b) {
+            return (zero(), one(), zero(), zero(), one());
+        }
+        let (mut r0, mut r1) = (a.clone(), b.clone());
+        let (mut s0, mut s1) = (one(), zero());
+        let (mut t0, mut t1) = (zero(), one());
+        while !<Self as CRing>::is_zero(&r1) {
+            let (q, r) = r0.div_rem(&r1);
+            r0 = std::mem::replace(&mut r1, r);
+            let s = s0 - q.clone() * s1.clone();
+            s0 = std::mem::replace(&mut s1, s);
+            let t = t0 - q * t1.clone();
+            t0 = std::mem::replace(&mut t1, t);
+        }
+        let (u, v) = (-b.div_rem(&r0).0, a.div_rem(&r0).0);
+        (r0, s0, t0, u, v)
+    }
+
+    fn divide(a: Self, b: Self) -> Option<Self> {
+        if <Self as CRing>::is_zero(&b) {
+            return <Self as CRing>::is_zero(&a).then_some(b);
+        }
+        let (q, r) = a.div_rem(&b);
+        <Self as CRing>::is_zero(&r).then_some(q)
+    }
+
+    fn rem(a: Self, b: Self) -> Self {
+        match <Self as CRing>::is_zero(&b) {
+            true => a,
+            false => a.div_rem(&b).1,
+        }
+    }
+
+    fn is_unit(&self) -> bool {
+        self.degree() == Some(0)
+    }
+
+    fn annihilator(&self) -> Self {
+        match <Self as CRing>::is_zero(self) {
+            true => <Self as CRing>::one(),
+            false => <Self as CRing>::zero(),
+        }
+    }
+
+    /// The monic associate
+    fn canonical_associate(&self) -> (Self, Self) {
+        match self.leading_coefficient().inv() {
+            Some(inv) => {
+                let unit = Self::monomial(inv, 0);
+                (self.clone() * unit.clone(), unit)
+            }
+            None => (self.clone(), <Self as CRing>::one()),
+        }
+    }
+
+    fn norm(&self) -> usize {
+        self.degree().unwrap_or(0)
+    }
+}
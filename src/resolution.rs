@@ -9,7 +9,7 @@ use itertools::Itertools;
 use crate::{
     comodule::traits::{Comodule, ComoduleMorphism},
     export::{Page, SSeq},
-    linalg::grading::Grading,
+    linalg::grading::{Grading, LimitPolicy},
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -28,21 +28,26 @@ impl<G: Grading, M: Comodule<G>> Resolution<G, M> {
         }
     }
 
-    pub fn resolve_to_s(&mut self, s: usize, mut limit: G) {
+    pub fn resolve_to_s(&mut self, s: usize, limit: G) {
+        self.resolve_to_s_with_policy(s, limit, LimitPolicy::default());
+    }
+
+    /// Resolves with `limit` in homological degree zero, changing it according to `policy`
+    pub fn resolve_to_s_with_policy(&mut self, s: usize, mut limit: G, policy: LimitPolicy<G>) {
         if self.resolution.len() == 0 {
             let zero_morph = M::Morphism::zero_morphism(self.comodule.clone());
 
-            let initial_inject = zero_morph.inject_codomain_to_cofree(limit);
+            let initial_inject = zero_morph.inject_codomain_to_cofree(limit, policy);
             self.resolution.push(initial_inject);
         }
 
         for _ in self.resolution.len()..=s {
-            // Increment limit and get last morphism
-            limit = limit.incr();
+            // Update limit and get last morphism
+            limit = policy.next(limit);
             let last_morph = self.resolution.last().unwrap();
 
             let coker = last_morph.cokernel();
-            let inject = coker.inject_codomain_to_cofree(limit);
+            let inject = coker.inject_codomain_to_cofree(limit, policy);
             let combine = M::Morphism::compose(inject, coker);
 
             self.resolution.push(combine);
@@ -50,7 +55,17 @@ impl<G: Grading, M: Comodule<G>> Resolution<G, M> {
     }
 
     /// This crashes on WASM
-    pub fn resolve_to_s_with_print(&mut self, s: usize, mut limit: G) {
+    pub fn resolve_to_s_with_print(&mut self, s: usize, limit: G) {
+        self.resolve_to_s_with_print_and_policy(s, limit, LimitPolicy::default());
+    }
+
+    /// This crashes on WASM
+    pub fn resolve_to_s_with_print_and_policy(
+        &mut self,
+        s: usize,
+        mut limit: G,
+        policy: LimitPolicy<G>,
+    ) {
         println!("Resolving to filtration index: {} \n", s);
 
         if self.resolution.len() == 0 {
@@ -63,13 +78,13 @@ impl<G: Grading, M: Comodule<G>> Resolution<G, M> {
 
             println!("took: {:.2?}\n", inject_time.elapsed());
 
-            let initial_inject = zero_morph.inject_codomain_to_cofree(limit);
+            let initial_inject = zero_morph.inject_codomain_to_cofree(limit, policy);
             self.resolution.push(initial_inject);
         }
 
         for i in self.resolution.len()..=s {
-            // Increment limit and get last morphism
-            limit = limit.incr();
+            // Update limit and get last morphism
+            limit = policy.next(limit);
             let last_morph = self.resolution.last().unwrap();
 
             println!("Resolving for {}", i);
@@ -84,7 +99,7 @@ impl<G: Grading, M: Comodule<G>> Resolution<G, M> {
             print!("Injecting cokernel          ");
             io::stdout().flush().unwrap();
             // Inject to cofree
-            let inject = coker.inject_codomain_to_cofree(limit);
+            let inject = coker.inject_codomain_to_cofree(limit, policy);
             println!("took: {:.2?}", inject_time.elapsed());
 
             let compose_time = std::time::Instant::now();
@@ -104,7 +119,17 @@ impl<G: Grading, M: Comodule<G>> Resolution<G, M> {
 
     pub fn generate_sseq(&self, name: &str) -> SSeq {
         let (x_formula, y_formula) = G::default_formulas();
+        self.generate_sseq_with_formulas(name, &x_formula, &y_formula)
+    }
 
+    /// Like `generate_sseq`, with the formulas for the axes given in terms of
+    /// `s` and the degree names of the grading instead of the defaults
+    pub fn generate_sseq_with_formulas(
+        &self,
+        name: &str,
+        x_formula: &str,
+        y_formula: &str,
+    ) -> SSeq {
         let gens = self
             .resolution
             .iter()
@@ -140,8 +165,8 @@ impl<G: Grading, M: Comodule<G>> Resolution<G, M> {
         SSeq {
             name: name.to_owned(),
             degrees: G::degree_names(),
-            x_formula,
-            y_formula,
+            x_formula: x_formula.to_owned(),
+            y_formula: y_formula.to_owned(),
             pages: vec![page],
             differentials: vec![],
         }
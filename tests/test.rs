@@ -6,10 +6,23 @@ mod tests {
         comodule::{
             kcoalgebra::{kCoalgebra, A0_coalgebra},
             kcomodule::kComodule,
-            traits::Comodule,
+            kmorphism::kComoduleMorphism,
+            parsers::{parse_field, ParseOptions},
+            traits::{Comodule, ComoduleMorphism},
         },
+        dispatch::resolve,
         export::SSeq,
-        linalg::{field::F2, grading::UniGrading, row_matrix::RowMatrix},
+        linalg::{
+            extension_field::F4,
+            f2_matrix::F2Matrix,
+            field::{CRing, Fp, Fp32, F2},
+            flat_matrix::FlatMatrix,
+            fp_matrix::FpMatrix,
+            grading::{BiGrading, Grading, LimitPolicy, MotivicGrading, MultiGrading, UniGrading},
+            rational::Q,
+            row_matrix::RowMatrix,
+            sparse_matrix::SparseMatrix,
+        },
         resolution::Resolution,
     };
     use itertools::Itertools;
@@ -93,7 +106,7 @@ mod tests {
             ]
         );
 
-        let sorted_lines: Vec<((usize, usize), (usize, usize), usize, String)> = page
+        let sorted_lines: Vec<((usize, usize), (usize, usize), String, String)> = page
             .structure_lines
             .iter()
             .map(|x| (x.clone()))
@@ -102,10 +115,10 @@ mod tests {
         assert_eq!(
             sorted_lines,
             vec![
-                ((0, 0), (1, 0), 1, "h_0".to_string()),
-                ((1, 0), (2, 0), 1, "h_0".to_string()),
-                ((2, 0), (3, 0), 1, "h_0".to_string()),
-                ((3, 0), (4, 0), 1, "h_0".to_string()),
+                ((0, 0), (1, 0), "1".to_string(), "h_0".to_string()),
+                ((1, 0), (2, 0), "1".to_string(), "h_0".to_string()),
+                ((2, 0), (3, 0), "1".to_string(), "h_0".to_string()),
+                ((3, 0), (4, 0), "1".to_string(), "h_0".to_string()),
             ]
         );
     }
@@ -143,6 +156,506 @@ mod tests {
         assert_eq!(p, comp_p);
     }
 
+    #[test]
+    fn test_a1_resolution_packed() {
+        let input = include_str!("../examples/direct/A(1).txt");
+        let coalgebra = Arc::new(kCoalgebra::parse(input, i32::MAX).unwrap().0);
+
+        let fp = kComodule::fp_comodule(coalgebra);
+
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, F2, F2Matrix>> =
+            Resolution::new(fp);
+
+        res.resolve_to_s(20, 20);
+
+        let p = res.generate_sseq("A(1)");
+        let comp_p: SSeq = serde_json::from_str(include_str!("./A(1).json")).unwrap();
+        assert_eq!(p, comp_p);
+    }
+
+    #[test]
+    fn test_a2_resolution_sparse() {
+        let input = include_str!("../examples/polynomial/A(2).txt");
+
+        let coalgebra = Arc::new(kCoalgebra::parse(input, i32::MAX - 10).unwrap().0);
+
+        let fp = kComodule::fp_comodule(coalgebra);
+
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, F2, SparseMatrix<F2>>> =
+            Resolution::new(fp);
+
+        res.resolve_to_s(20, 20);
+
+        let p = res.generate_sseq("A(2)");
+        let comp_p: SSeq = serde_json::from_str(include_str!("./A(2).json")).unwrap();
+        assert_eq!(p, comp_p);
+    }
+
+    #[test]
+    fn test_sseq_independent_of_threads() {
+        let sseq_json = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| {
+                let input = include_str!("../examples/polynomial/A(2).txt");
+                let coalgebra = Arc::new(kCoalgebra::parse(input, 30).unwrap().0);
+                let fp = kComodule::fp_comodule(coalgebra);
+                let mut res: Resolution<UniGrading, kComodule<UniGrading, F2, RowMatrix<F2>>> =
+                    Resolution::new(fp);
+                res.resolve_to_s(10, 30);
+                res.generate_sseq("A(2)").to_string()
+            })
+        };
+
+        let single = sseq_json(1);
+        assert_eq!(single, sseq_json(1));
+        assert_eq!(single, sseq_json(4));
+    }
+
+    #[test]
+    fn test_p3_resolution_packed() {
+        let input = include_str!("../examples/polynomial/P(3).txt");
+
+        let coalgebra = Arc::new(kCoalgebra::parse(input, 60).unwrap().0);
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, Fp<3>, FlatMatrix<Fp<3>>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(6, 60);
+
+        let coalgebra = Arc::new(kCoalgebra::parse(input, 60).unwrap().0);
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut packed: Resolution<UniGrading, kComodule<UniGrading, Fp<3>, FpMatrix<3>>> =
+            Resolution::new(fp);
+        packed.resolve_to_s(6, 60);
+
+        assert_eq!(res.generate_sseq("P"), packed.generate_sseq("P"));
+    }
+
+    #[test]
+    fn test_dispatch_resolution() {
+        let input = include_str!("../examples/direct/A(1).txt");
+        let p = resolve(input, None, 20, 20, "A(1)", false).unwrap();
+        let comp_p: SSeq = serde_json::from_str(include_str!("./A(1).json")).unwrap();
+        assert_eq!(p, comp_p);
+
+        let input = include_str!("../examples/polynomial/P(3).txt");
+        let coalgebra = Arc::new(kCoalgebra::parse(input, 60).unwrap().0);
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, Fp<3>, FlatMatrix<Fp<3>>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(6, 60);
+        assert_eq!(
+            resolve(input, None, 6, 60, "P", false).unwrap(),
+            res.generate_sseq("P")
+        );
+    }
+
+    #[test]
+    fn test_dispatch_fields() {
+        assert_eq!(parse_field("# comment\n- FIELD\n\n 5 \n- BASIS"), Ok(5));
+        assert!(parse_field("- BASIS\n1: 0").is_err());
+        assert!(parse_field("- FIELD\nx").is_err());
+
+        let input = include_str!("../examples/direct/A(0).txt");
+        assert!(resolve(&input.replacen("2", "6", 1), None, 2, 10, "", false).is_err());
+        assert!(resolve(&input.replacen("2", "4", 1), None, 2, 10, "", false).is_ok());
+    }
+
+    #[test]
+    fn test_rational_polynomial_resolution() {
+        // Q[x] with x primitive has Ext an exterior algebra on one class h_0
+        let input = "- FIELD\n0\n\n- GENERATOR\nx: 2\n\n- RELATION\n\n- COACTION\nx: 1|x + x|1";
+        let coalgebra = Arc::new(kCoalgebra::parse(input, 20).unwrap().0);
+
+        let fp = kComodule::fp_comodule(coalgebra);
+
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, Q, FlatMatrix<Q>>> =
+            Resolution::new(fp);
+
+        res.resolve_to_s(4, 20);
+
+        let sseq = res.generate_sseq("Q[x]");
+        let gens: Vec<(usize, Vec<i32>)> = sseq.pages[0]
+            .generators
+            .iter()
+            .map(|x| (x.0, x.2.clone()))
+            .sorted()
+            .collect();
+        assert_eq!(gens, vec![(0, vec![0]), (1, vec![2])]);
+    }
+
+    #[test]
+    fn test_rational_structure_lines() {
+        // Q[x, y] with x and y primitive, so Ext is exterior on h_0 and h_1
+        let input = "- FIELD\n0\n\n- GENERATOR\nx: 2\ny: 4\n\n- RELATION\n\n- COACTION\n\
+            x: 1|x + x|1\ny: 1|y + y|1";
+        let coalgebra = Arc::new(kCoalgebra::parse(input, 20).unwrap().0);
+
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, Q, FlatMatrix<Q>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(3, 20);
+
+        let sseq = res.generate_sseq("Q[x, y]");
+        let lines = &sseq.pages[0].structure_lines;
+        assert!(lines.iter().all(|x| !Q::parse(&x.2).unwrap().is_zero()));
+        // h_0 h_1 is reached from h_0 and from h_1 with opposite signs
+        assert!(lines.contains(&((1, 1), (2, 0), "-1".to_string(), "h_0".to_string())));
+    }
+
+    #[test]
+    fn test_large_prime_polynomial_resolution() {
+        // At p = 65521 the first p-th power x^p is far beyond the limit, so this agrees with Q
+        let input = "- FIELD\n65521\n\n- GENERATOR\nx: 2\n\n- RELATION\n\n- COACTION\nx: 1|x + x|1";
+        let coalgebra = Arc::new(kCoalgebra::parse(input, 20).unwrap().0);
+
+        let fp = kComodule::fp_comodule(coalgebra);
+
+        let mut res: Resolution<
+            UniGrading,
+            kComodule<UniGrading, Fp32<65521>, FlatMatrix<Fp32<65521>>>,
+        > = Resolution::new(fp);
+
+        res.resolve_to_s(4, 20);
+
+        let sseq = res.generate_sseq("F_p[x]");
+        let gens: Vec<(usize, Vec<i32>)> = sseq.pages[0]
+            .generators
+            .iter()
+            .map(|x| (x.0, x.2.clone()))
+            .sorted()
+            .collect();
+        assert_eq!(gens, vec![(0, vec![0]), (1, vec![2])]);
+    }
+
+    #[test]
+    fn test_multigraded_resolution() {
+        // A(0) with xi1 in degree (1, 0, 1) agrees with the single grading in the first degree
+        let coalgebra = "- FIELD\n2\n\n- GENERATOR\nxi1: {}\n\n- RELATION\nxi1^2\n\n- COACTION\nxi1: 1|xi1 + xi1|1";
+        let comodule = "- BASIS\na1: {0}\naq0: {0}\naq1: {1}\n\n- COACTION\na1: 1|a1\naq0: 1|aq0\naq1: 1|aq1 + xi1|aq0";
+
+        let (uni, translate) =
+            kCoalgebra::<UniGrading, F2, FlatMatrix<F2>>::parse(&coalgebra.replace("{}", "1"), 20)
+                .unwrap();
+        let comod = kComodule::parse(
+            &comodule.replace("{0}", "0").replace("{1}", "1"),
+            Arc::new(uni),
+            &translate,
+            20,
+        )
+        .unwrap();
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, F2, FlatMatrix<F2>>> =
+            Resolution::new(comod);
+        res.resolve_to_s(5, 20);
+        let uni_sseq = res.generate_sseq("A(0)");
+
+        let limit = MultiGrading([20, 20, 20]);
+        let (multi, translate) = kCoalgebra::<MultiGrading<3>, F2, FlatMatrix<F2>>::parse(
+            &coalgebra.replace("{}", "(1, 0, 1)"),
+            limit,
+        )
+        .unwrap();
+        let comod = kComodule::parse(
+            &comodule
+                .replace("{0}", "(0, 0, 0)")
+                .replace("{1}", "(1, 0, 1)"),
+            Arc::new(multi),
+            &translate,
+            limit,
+        )
+        .unwrap();
+        let mut res: Resolution<MultiGrading<3>, kComodule<MultiGrading<3>, F2, FlatMatrix<F2>>> =
+            Resolution::new(comod);
+        res.resolve_to_s(5, limit);
+        let multi_sseq = res.generate_sseq_with_formulas("A(0)", "t-s", "u");
+
+        assert_eq!(multi_sseq.degrees, MultiGrading::<3>::degree_names());
+        assert_eq!(multi_sseq.y_formula, "u");
+        for gen in &multi_sseq.pages[0].generators {
+            assert_eq!(gen.2, vec![gen.2[0], 0, gen.2[0]]);
+        }
+        let grades = |sseq: &SSeq| -> Vec<(usize, i32)> {
+            sseq.pages[0]
+                .generators
+                .iter()
+                .map(|x| (x.0, x.2[0]))
+                .sorted()
+                .collect()
+        };
+        assert_eq!(grades(&multi_sseq), grades(&uni_sseq));
+        assert_eq!(uni_sseq.pages[0].generators.len(), 7);
+    }
+
+    #[test]
+    fn test_nonpositive_generator_rejected() {
+        // x^k stays within every componentwise limit if x has a negative degree
+        let input =
+            "- FIELD\n2\n\n- GENERATOR\nx: (2, -1, 0)\n\n- RELATION\n\n- COACTION\nx: 1|x + x|1";
+        let limit = MultiGrading([20, 20, 20]);
+        assert!(kCoalgebra::<MultiGrading<3>, F2, FlatMatrix<F2>>::parse(input, limit).is_err());
+
+        let exterior = input.replace("(2, -1, 0)", "(2, -1, 0) exterior");
+        let (coalgebra, _) =
+            kCoalgebra::<MultiGrading<3>, F2, FlatMatrix<F2>>::parse(&exterior, limit).unwrap();
+        assert_eq!(
+            coalgebra
+                .space
+                .dimension_in_grade(&MultiGrading([2, -1, 0])),
+            1
+        );
+    }
+
+    #[test]
+    fn test_bigraded_truncation() {
+        // h_0^s lives in (s, s), so a fixed limit (10, 3) only keeps s <= 3
+        let input = "- FIELD\n2\n\n- GENERATOR\nxi1: 1, 1\n\n- RELATION\nxi1^2\n\n- COACTION\nxi1: 1|xi1 + xi1|1";
+        let limit = BiGrading(10, 3);
+        let coalgebra = Arc::new(
+            kCoalgebra::<BiGrading, F2, FlatMatrix<F2>>::parse(input, limit)
+                .unwrap()
+                .0,
+        );
+
+        let fp = kComodule::fp_comodule(coalgebra.clone());
+        let mut res: Resolution<BiGrading, kComodule<BiGrading, F2, FlatMatrix<F2>>> =
+            Resolution::new(fp);
+        res.resolve_to_s_with_policy(6, limit, LimitPolicy::Fixed);
+        let gens: Vec<(usize, Vec<i32>)> = res.generate_sseq("A(0)").pages[0]
+            .generators
+            .iter()
+            .map(|x| (x.0, x.2.clone()))
+            .sorted()
+            .collect();
+        assert_eq!(
+            gens,
+            (0..=3).map(|s| (s, vec![s as i32, s as i32])).collect_vec()
+        );
+
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<BiGrading, kComodule<BiGrading, F2, FlatMatrix<F2>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(6, limit);
+        assert_eq!(res.generate_sseq("A(0)").pages[0].generators.len(), 7);
+    }
+
+    #[test]
+    fn test_fixed_policy_stays_within_limit() {
+        let input = "- FIELD\n2\n\n- GENERATOR\nx: 1, 1\ny: 1, 0\n\n- RELATION\n\n- COACTION\nx: 1|x + x|1\ny: 1|y + y|1";
+        let limit = BiGrading(4, 2);
+        let fixed = ParseOptions {
+            validate: false,
+            policy: LimitPolicy::Fixed,
+        };
+        type Coalgebra = kCoalgebra<BiGrading, F2, FlatMatrix<F2>>;
+
+        // The default policy builds past the limit, a fixed one does not
+        let padded = Coalgebra::parse(input, limit).unwrap().0;
+        assert!(padded.space.0.keys().any(|g| !g.within(limit)));
+        let coalgebra = Arc::new(
+            Coalgebra::parse_with_options(input, limit, fixed)
+                .unwrap()
+                .0,
+        );
+        assert!(coalgebra.space.0.keys().all(|g| g.within(limit)));
+
+        let fp = Arc::new(kComodule::fp_comodule(coalgebra.clone()));
+        let inject = kComoduleMorphism::zero_morphism(fp)
+            .inject_codomain_to_cofree(limit, LimitPolicy::Fixed);
+        assert!(inject.codomain.space.0.keys().all(|g| g.within(limit)));
+
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<BiGrading, kComodule<BiGrading, F2, FlatMatrix<F2>>> =
+            Resolution::new(fp);
+        res.resolve_to_s_with_policy(6, limit, LimitPolicy::Fixed);
+        let gens = &res.generate_sseq("P(x, y)").pages[0].generators;
+        assert!(!gens.is_empty());
+        assert!(gens.iter().all(|x| BiGrading(x.2[0], x.2[1]).within(limit)));
+    }
+
+    #[test]
+    fn test_motivic_resolution() {
+        let input = include_str!("../examples/motivic/A(1)_mod_tau.txt");
+        let limit = MotivicGrading(20, 10);
+        let coalgebra = Arc::new(
+            kCoalgebra::<MotivicGrading, F2, FlatMatrix<F2>>::parse(input, limit)
+                .unwrap()
+                .0,
+        );
+
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<MotivicGrading, kComodule<MotivicGrading, F2, FlatMatrix<F2>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(3, limit);
+
+        let (x_formula, y_formula) = MotivicGrading::chow_formulas();
+        let sseq = res.generate_sseq_with_formulas("A(1)/tau", &x_formula, &y_formula);
+        assert_eq!(sseq.degrees, vec!['t', 'w']);
+        assert_eq!(sseq.y_formula, "t-2w");
+
+        // h_0 from tau_0 and h_1 from xi_1
+        let ext_1: Vec<Vec<i32>> = sseq.pages[0]
+            .generators
+            .iter()
+            .filter(|x| x.0 == 1)
+            .map(|x| x.2.clone())
+            .sorted()
+            .collect();
+        assert_eq!(ext_1, vec![vec![1, 0], vec![2, 1]]);
+    }
+
+    #[test]
+    fn test_motivic_tau() {
+        // tau^k has coweight k, so only tau^0, ..., tau^3 and tau^0 tau0, ..., tau^2 tau0 fit
+        let input = include_str!("../examples/motivic/A(0).txt");
+        let limit = MotivicGrading(6, 3);
+        let coalgebra = Arc::new(
+            kCoalgebra::<MotivicGrading, F2, FlatMatrix<F2>>::parse(input, limit)
+                .unwrap()
+                .0,
+        );
+        assert_eq!(
+            coalgebra.space.0.values().map(|v| v.len()).sum::<usize>(),
+            7
+        );
+        assert_eq!(
+            coalgebra.space.dimension_in_grade(&MotivicGrading(0, -3)),
+            1
+        );
+        assert_eq!(
+            coalgebra.space.dimension_in_grade(&MotivicGrading(0, -4)),
+            0
+        );
+
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<MotivicGrading, kComodule<MotivicGrading, F2, FlatMatrix<F2>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(2, limit);
+
+        // Away from the coweight limit: h_0 from tau_0, and tau and tau^2 which are primitive
+        let sseq = res.generate_sseq("A(0)");
+        let ext_1: Vec<Vec<i32>> = sseq.pages[0]
+            .generators
+            .iter()
+            .filter(|x| x.0 == 1 && MotivicGrading(x.2[0], x.2[1]).coweight() < 3)
+            .map(|x| x.2.clone())
+            .sorted()
+            .collect();
+        assert_eq!(ext_1, vec![vec![0, -2], vec![0, -1], vec![1, 0]]);
+    }
+
+    #[test]
+    fn test_a1_p3_resolution() {
+        let input = include_str!("../examples/polynomial/A(1)_p3.txt");
+        let coalgebra = Arc::new(
+            kCoalgebra::<UniGrading, Fp<3>, RowMatrix<Fp<3>>>::parse(input, 30)
+                .unwrap()
+                .0,
+        );
+
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, Fp<3>, RowMatrix<Fp<3>>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(3, 30);
+
+        let sseq = res.generate_sseq("A(1) at p=3");
+
+        // a_0 from tau_0 and h_0 from xi_1, tau_1 is not primitive
+        let ext_1: Vec<i32> = sseq.pages[0]
+            .generators
+            .iter()
+            .filter(|x| x.0 == 1)
+            .map(|x| x.2[0])
+            .sorted()
+            .collect();
+        assert_eq!(ext_1, vec![1, 4]);
+    }
+
+    #[test]
+    fn test_structure_lines_of_sum_primitive() {
+        // x + y is the only primitive in grade 2, so h_1 is dual to x and y alike
+        let input = "- FIELD\n2\n\n- BASIS\n1: 0\na: 1\nx: 2\ny: 2\n\n- COACTION\n\
+            1: 1|1\na: 1|a + a|1\nx: 1|x + x|1 + a|a\ny: 1|y + y|1 + a|a\n";
+        let coalgebra = Arc::new(
+            kCoalgebra::<UniGrading, F2, RowMatrix<F2>>::parse(input, 10)
+                .unwrap()
+                .0,
+        );
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, F2, RowMatrix<F2>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(1, 10);
+        let sseq = res.generate_sseq("sum");
+
+        let labels: Vec<String> = sseq.pages[0]
+            .structure_lines
+            .iter()
+            .filter(|x| x.0 == (0, 0))
+            .map(|x| x.3.clone())
+            .sorted()
+            .collect();
+        assert_eq!(labels, vec!["h_0", "h_1"]);
+    }
+
+    #[test]
+    fn test_structure_lines_over_f4() {
+        let input = "- FIELD\n4\n\n- BASIS\n1: 0\na: 1\nx: 2\ny: 2\n\n- COACTION\n\
+            1: 1|1\na: 1|a + a|1\nx: 1|x + x|1 + a|a\ny: 1|y + y|1 + w.a|a\n";
+        let coalgebra = Arc::new(
+            kCoalgebra::<UniGrading, F4, RowMatrix<F4>>::parse(input, 10)
+                .unwrap()
+                .0,
+        );
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, F4, RowMatrix<F4>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(2, 10);
+        let sseq = res.generate_sseq("w");
+
+        // Values are field elements, w + 1 is the inverse of the w in the coaction of y
+        let values: Vec<F4> = sseq.pages[0]
+            .structure_lines
+            .iter()
+            .map(|x| F4::parse(&x.2).unwrap())
+            .collect();
+        assert!(!values.is_empty());
+        assert!(values.iter().all(|v| !v.is_zero()));
+        assert!(values.contains(&(F4::generator() + F4::one())));
+    }
+
+    #[test]
+    fn test_odd_primary_steenrod_resolution() {
+        let input = include_str!("../examples/polynomial/A_p3.txt");
+        let coalgebra = Arc::new(
+            kCoalgebra::<UniGrading, Fp<3>, RowMatrix<Fp<3>>>::parse(input, 40)
+                .unwrap()
+                .0,
+        );
+        let fp = kComodule::fp_comodule(coalgebra);
+        let mut res: Resolution<UniGrading, kComodule<UniGrading, Fp<3>, RowMatrix<Fp<3>>>> =
+            Resolution::new(fp);
+        res.resolve_to_s(2, 40);
+        let sseq = res.generate_sseq("A at p=3");
+
+        let ext = |s: usize| -> Vec<i32> {
+            sseq.pages[0]
+                .generators
+                .iter()
+                .filter(|x| x.0 == s)
+                .map(|x| x.2[0])
+                .sorted()
+                .collect()
+        };
+        // a_0 from tau_0 and h_i from xi_1^{3^i}, nothing from tau_1 or xi_2
+        assert_eq!(ext(1), vec![1, 4, 12, 36]);
+        // a_0^2 is the only class in degree 2
+        assert_eq!(
+            ext(2).iter().filter(|&&t| t <= 4).collect::<Vec<_>>(),
+            vec![&2]
+        );
+    }
+
     #[test]
     fn test_a2_resolution_direct() {
         let input = include_str!("../examples/direct/A(2).txt");